@@ -0,0 +1,401 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use crate::{calendar::unix_now, config::Config, Day};
+use std::{
+    fmt::Display,
+    fs::{self, create_dir_all},
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+    sync::OnceLock,
+    time::UNIX_EPOCH,
+};
+
+/// Where fetch timestamps are recorded, relative to the project root. Not checked into git.
+const FETCH_LOG_FILE: &str = ".aoc_fetch_log";
+
+/// Minimum number of seconds between two fetches of the same resource.
+const MIN_FETCH_INTERVAL: u64 = 60;
+
+pub enum AocCliError {
+    CommandNotFound,
+    CommandNotCallable,
+    BadExitStatus(Output),
+    IoError,
+}
+
+impl Display for AocCliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocCliError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
+            AocCliError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
+            AocCliError::BadExitStatus(_) => {
+                write!(f, "aoc-cli exited with a non-zero status.")
+            }
+            AocCliError::IoError => write!(f, "could not write output files to file system."),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resource {
+    Input,
+    Puzzle,
+}
+
+impl Resource {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Resource::Input => "input",
+            Resource::Puzzle => "puzzle",
+        }
+    }
+}
+
+/// Records when each resource was last fetched, keyed by year, day and resource.
+///
+/// The log is a plain text file with one `<year> <day> <resource> <unix timestamp>` entry per line.
+/// `year` is `latest` when no year was passed to aoc-cli.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct FetchLog {
+    entries: Vec<(String, Day, String, u64)>,
+}
+
+impl FetchLog {
+    pub fn parse(contents: &str) -> Self {
+        let entries = contents
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.split_whitespace();
+                let year = parts.next()?.to_string();
+                let day = parts.next()?.parse().ok()?;
+                let resource = parts.next()?.to_string();
+                let timestamp = parts.next()?.parse().ok()?;
+                Some((year, day, resource, timestamp))
+            })
+            .collect();
+
+        Self { entries }
+    }
+
+    pub fn load() -> Self {
+        fs::read_to_string(Config::get().root.join(FETCH_LOG_FILE))
+            .map(|contents| Self::parse(&contents))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> Result<(), AocCliError> {
+        fs::write(Config::get().root.join(FETCH_LOG_FILE), self.to_string())
+            .map_err(|_| AocCliError::IoError)
+    }
+
+    pub fn last_fetched(&self, day: Day, year: Option<u16>, resource: Resource) -> Option<u64> {
+        let year = year_key(year);
+        self.entries
+            .iter()
+            .find(|(y, d, r, _)| *y == year && *d == day && r == resource.as_str())
+            .map(|(_, _, _, timestamp)| *timestamp)
+    }
+
+    pub fn record(&mut self, day: Day, year: Option<u16>, resource: Resource, timestamp: u64) {
+        let year = year_key(year);
+        match self
+            .entries
+            .iter_mut()
+            .find(|(y, d, r, _)| *y == year && *d == day && r == resource.as_str())
+        {
+            Some(entry) => entry.3 = timestamp,
+            None => self
+                .entries
+                .push((year, day, resource.as_str().into(), timestamp)),
+        }
+    }
+
+    /// Seconds left until `resource` may be fetched again, if it was fetched too recently.
+    pub fn cooldown(
+        &self,
+        day: Day,
+        year: Option<u16>,
+        resource: Resource,
+        now: u64,
+    ) -> Option<u64> {
+        self.last_fetched(day, year, resource)
+            .map(|last| last + MIN_FETCH_INTERVAL)
+            .filter(|&next| next > now)
+            .map(|next| next - now)
+    }
+}
+
+impl Display for FetchLog {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (year, day, resource, timestamp) in &self.entries {
+            writeln!(f, "{year} {day} {resource} {timestamp}")?;
+        }
+        Ok(())
+    }
+}
+
+fn year_key(year: Option<u16>) -> String {
+    year.map_or_else(|| "latest".into(), |year| year.to_string())
+}
+
+fn modified_at(path: &Path) -> Option<u64> {
+    fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+        .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+}
+
+fn is_non_empty_file(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|meta| meta.len() > 0)
+}
+
+pub fn check() -> Result<(), AocCliError> {
+    Command::new("aoc")
+        .arg("-V")
+        .output()
+        .map_err(|_| AocCliError::CommandNotFound)?;
+    Ok(())
+}
+
+pub fn read(day: Day, year: Option<u16>) -> Result<Output, AocCliError> {
+    // TODO: output local puzzle if present.
+    let args = build_args("read", &[], day, year);
+    call_aoc_cli(&args)
+}
+
+/// Submits the answer for a part of a day.
+pub fn submit(day: Day, year: Option<u16>, part: u8, answer: &str) -> Result<Output, AocCliError> {
+    let mut args = build_args("submit", &[], day, year);
+    args.extend([part.to_string(), answer.into()]);
+    call_aoc_cli(&args)
+}
+
+/// The part passed to a solution with `--submit <part>` or `--submit=<part>`.
+fn submit_part_from_args(args: impl Iterator<Item = String>) -> Option<Result<u8, String>> {
+    let mut args = args.take_while(|arg| arg != "--");
+    while let Some(arg) = args.next() {
+        let value = match arg.strip_prefix("--submit") {
+            Some("") => args.next().unwrap_or_default(),
+            Some(rest) if rest.starts_with('=') => rest[1..].to_string(),
+            _ => continue,
+        };
+        return Some(match value.as_str() {
+            "1" => Ok(1),
+            "2" => Ok(2),
+            _ => Err(format!("--submit expects part 1 or 2, got \"{value}\".")),
+        });
+    }
+    None
+}
+
+static SUBMIT_PART: OnceLock<Option<u8>> = OnceLock::new();
+
+/// Submits `answer` if the solution `bin` was run with `--submit <part>` for this part. Called by `solve!`,
+/// which passes the decoded letters for answers drawn in block letters.
+pub fn submit_if_requested(bin: &str, part: u8, answer: &str) {
+    let requested = SUBMIT_PART.get_or_init(|| {
+        submit_part_from_args(std::env::args().skip(1))
+            .and_then(|part| part.map_err(|e| eprintln!("{e}")).ok())
+    });
+    if *requested != Some(part) {
+        return;
+    }
+
+    let Ok(day) = bin.parse::<Day>() else {
+        eprintln!("Can not submit from \"{bin}\", the binary is not named after a day.");
+        return;
+    };
+    if check().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        return;
+    }
+
+    println!("Submitting \"{answer}\" for part {part}...");
+    if let Err(e) = submit(day, Config::get().year, part, answer) {
+        eprintln!("failed to spawn aoc-cli: {e}");
+    }
+}
+
+/// Downloads the input and puzzle description for a day.
+///
+/// An existing non-empty input is kept, since it never changes once released.
+/// The puzzle description is refreshed to pick up part two, unless it was edited by hand since the last fetch.
+/// `force` overwrites both regardless. Fetches of the same resource are throttled to one per minute.
+pub fn download(day: Day, year: Option<u16>, force: bool) -> Result<(), AocCliError> {
+    let config = Config::get();
+    let input_path = get_input_path(day);
+    let puzzle_path = get_puzzle_path(day);
+    create_dir_all(&config.paths.puzzles).map_err(|_| AocCliError::IoError)?;
+
+    let mut log = FetchLog::load();
+
+    if !force && is_non_empty_file(&input_path) {
+        println!(
+            "Input \"{}\" already exists, skipping.",
+            config.display_path(&input_path)
+        );
+    } else if fetch(&mut log, day, year, Resource::Input, &input_path)? {
+        println!(
+            "🎄 Successfully wrote input to \"{}\".",
+            config.display_path(&input_path)
+        );
+    }
+
+    let hand_edited = match (
+        modified_at(&puzzle_path),
+        log.last_fetched(day, year, Resource::Puzzle),
+    ) {
+        (Some(modified), Some(fetched)) => modified > fetched,
+        _ => false,
+    };
+
+    if !force && hand_edited {
+        println!(
+            "Puzzle \"{}\" was edited since the last download, skipping. Pass --force to overwrite it.",
+            config.display_path(&puzzle_path)
+        );
+    } else if fetch(&mut log, day, year, Resource::Puzzle, &puzzle_path)? {
+        println!(
+            "🎄 Successfully wrote puzzle to \"{}\".",
+            config.display_path(&puzzle_path)
+        );
+    }
+
+    Ok(())
+}
+
+/// Fetches a single resource unless it is cooling down. Returns whether aoc-cli was called.
+fn fetch(
+    log: &mut FetchLog,
+    day: Day,
+    year: Option<u16>,
+    resource: Resource,
+    path: &Path,
+) -> Result<bool, AocCliError> {
+    if let Some(wait) = log.cooldown(day, year, resource, unix_now()) {
+        println!(
+            "The {} for day {} was fetched less than {}s ago, try again in {}s.",
+            resource.as_str(),
+            day.into_inner(),
+            MIN_FETCH_INTERVAL,
+            wait
+        );
+        return Ok(false);
+    }
+
+    let (only_flag, file_flag) = match resource {
+        Resource::Input => ("--input-only", "--input-file"),
+        Resource::Puzzle => ("--puzzle-only", "--puzzle-file"),
+    };
+
+    let args = build_args(
+        "download",
+        &[
+            "--overwrite".into(),
+            only_flag.into(),
+            file_flag.into(),
+            path.display().to_string(),
+        ],
+        day,
+        year,
+    );
+
+    let output = call_aoc_cli(&args)?;
+
+    if !output.status.success() {
+        return Err(AocCliError::BadExitStatus(output));
+    }
+
+    log.record(day, year, resource, unix_now());
+    log.save()?;
+
+    Ok(true)
+}
+
+fn get_input_path(day: Day) -> PathBuf {
+    day.path(&Config::get().paths.inputs, "txt")
+}
+
+fn get_puzzle_path(day: Day) -> PathBuf {
+    day.path(&Config::get().paths.puzzles, "md")
+}
+
+fn build_args(command: &str, args: &[String], day: Day, year: Option<u16>) -> Vec<String> {
+    let mut cmd_args = args.to_vec();
+
+    if let Some(session_file) = &Config::get().session_file {
+        cmd_args.push("--session-file".into());
+        cmd_args.push(session_file.display().to_string());
+    }
+
+    if let Some(year) = year {
+        cmd_args.push("--year".into());
+        cmd_args.push(year.to_string());
+    }
+
+    cmd_args.append(&mut vec![
+        "--day".into(),
+        day.into_inner().to_string(),
+        command.into(),
+    ]);
+
+    cmd_args
+}
+
+fn call_aoc_cli(args: &[String]) -> Result<Output, AocCliError> {
+    if cfg!(debug_assertions) {
+        println!("Calling >aoc with: {}", args.join(" "));
+    }
+
+    Command::new("aoc")
+        .args(args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|_| AocCliError::CommandNotCallable)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_submit_part_from_args() {
+        let args = |args: &[&str]| submit_part_from_args(args.iter().map(|arg| arg.to_string()));
+        assert_eq!(args(&["--submit", "2"]), Some(Ok(2)));
+        assert_eq!(args(&["--visualize", "--submit=1"]), Some(Ok(1)));
+        assert!(matches!(args(&["--submit", "3"]), Some(Err(_))));
+        assert!(matches!(args(&["--submit"]), Some(Err(_))));
+        assert_eq!(args(&["--", "--submit", "1"]), None);
+        assert_eq!(args(&[]), None);
+    }
+
+    #[test]
+    fn test_fetch_log_roundtrip() {
+        let day = crate::day!(1);
+        let mut log = FetchLog::default();
+        log.record(day, None, Resource::Input, 100);
+        log.record(day, Some(2021), Resource::Puzzle, 200);
+        log.record(day, None, Resource::Input, 300);
+
+        let serialized = log.to_string();
+        assert_eq!(serialized, "latest 01 input 300\n2021 01 puzzle 200\n");
+        assert_eq!(FetchLog::parse(&serialized), log);
+    }
+
+    #[test]
+    fn test_fetch_log_cooldown() {
+        let day = crate::day!(1);
+        let mut log = FetchLog::default();
+        assert_eq!(log.cooldown(day, None, Resource::Puzzle, 1000), None);
+
+        log.record(day, None, Resource::Puzzle, 1000);
+        assert_eq!(log.cooldown(day, None, Resource::Puzzle, 1010), Some(50));
+        assert_eq!(log.cooldown(day, None, Resource::Puzzle, 1060), None);
+        assert_eq!(log.cooldown(day, None, Resource::Input, 1010), None);
+        assert_eq!(log.cooldown(day, Some(2022), Resource::Puzzle, 1010), None);
+    }
+}
@@ -1,43 +1,107 @@
-fn group_input(input: &str) -> Vec<u32> {
-    // First solution using imperative approach, which is a little faster:
-    // let mut group: Vec<u32> = vec![0];
-    // for value in input.lines() {
-    //     match value.parse::<u32>() {
-    //         Ok(num) => {
-    //             if let Some(last_num) = group.last_mut() {
-    //                 *last_num += num;
-    //             }
-    //         }
-    //         Err(_) => group.push(0),
-    //     }
-    // }
-    // group
+use std::io::BufRead;
 
+use advent_of_code::helpers::IterExt;
+
+/// Calories carried by each elf, read one line at a time so the input never has to fit into memory.
+fn groups(input: impl BufRead) -> impl Iterator<Item = u32> {
+    let mut lines = input
+        .lines()
+        .map(|line| line.expect("could not read input"));
+
+    std::iter::from_fn(move || {
+        let mut group = None;
+        for line in lines.by_ref() {
+            match line.trim().parse::<u32>() {
+                Ok(num) => *group.get_or_insert(0) += num,
+                // an empty line ends the group, unless there are several of them in a row.
+                Err(_) if group.is_some() => break,
+                Err(_) => {}
+            }
+        }
+        group
+    })
+}
+
+/// The most calories carried by one elf and the calories carried by the `n` elves carrying the most,
+/// in a single pass that keeps at most `n` groups in memory.
+fn calories(input: impl BufRead, n: usize) -> (u32, u32) {
+    let top = groups(input).top_k(n.max(1));
+    let max = top.first().copied().unwrap_or_default();
+    (max, top.into_iter().take(n).sum())
+}
+
+/// Collects the calories per elf by splitting the parsed lines at the empty ones.
+fn functional_groups(input: impl BufRead) -> Vec<u32> {
     // Parsing produces something similar to [Some(123), None, Some(123), Some(123)],
     // which can be then easily splited to create groups and sum them up
     input
         .lines()
-        .map(|val| val.parse::<u32>().ok())
+        .map(|line| line.expect("could not read input").parse::<u32>().ok())
         .collect::<Vec<Option<u32>>>()
         .split(|line| line.is_none())
         .map(|group| group.iter().map(|num| num.unwrap_or_default()).sum::<u32>())
         .collect()
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    group_input(input).into_iter().max()
+/// Collects the calories per elf by adding each line to the last group.
+fn imperative_groups(input: impl BufRead) -> Vec<u32> {
+    let mut group: Vec<u32> = vec![0];
+    for value in input.lines() {
+        match value.expect("could not read input").parse::<u32>() {
+            Ok(num) => {
+                if let Some(last_num) = group.last_mut() {
+                    *last_num += num;
+                }
+            }
+            Err(_) => group.push(0),
+        }
+    }
+    group
+}
+
+pub fn part_one(input: impl BufRead) -> Option<u32> {
+    Some(calories(input, 1).0)
+}
+
+pub fn part_one_functional(input: impl BufRead) -> Option<u32> {
+    functional_groups(input).into_iter().max()
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut groups = group_input(input);
-    groups.sort_unstable();
-    Some(groups.iter().rev().take(3).sum::<u32>())
+pub fn part_one_imperative(input: impl BufRead) -> Option<u32> {
+    imperative_groups(input).into_iter().max()
+}
+
+pub fn part_two(input: impl BufRead) -> Option<u32> {
+    Some(calories(input, 3).1)
+}
+
+pub fn part_two_functional(input: impl BufRead) -> Option<u32> {
+    Some(functional_groups(input).into_iter().sum_top_k(3))
+}
+
+pub fn part_two_imperative(input: impl BufRead) -> Option<u32> {
+    Some(imperative_groups(input).into_iter().sum_top_k(3))
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 1);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
+    advent_of_code::solve!(
+        1,
+        [
+            "streaming" => part_one,
+            "functional" => part_one_functional,
+            "imperative" => part_one_imperative,
+        ],
+        advent_of_code::open_file("inputs", advent_of_code::day!(1))
+    );
+    advent_of_code::solve!(
+        2,
+        [
+            "streaming" => part_two,
+            "functional" => part_two_functional,
+            "imperative" => part_two_imperative,
+        ],
+        advent_of_code::open_file("inputs", advent_of_code::day!(1))
+    );
 }
 
 #[cfg(test)]
@@ -46,13 +110,30 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 1);
-        assert_eq!(part_one(&input), Some(24000));
+        let input = advent_of_code::read_file("examples", advent_of_code::day!(1));
+        assert_eq!(part_one(input.as_bytes()), Some(24000));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 1);
-        assert_eq!(part_two(&input), Some(45000));
+        let input = advent_of_code::read_file("examples", advent_of_code::day!(1));
+        assert_eq!(part_two(input.as_bytes()), Some(45000));
+    }
+
+    #[test]
+    fn test_variants() {
+        let input = advent_of_code::read_file("examples", advent_of_code::day!(1));
+        assert_eq!(part_one_functional(input.as_bytes()), Some(24000));
+        assert_eq!(part_one_imperative(input.as_bytes()), Some(24000));
+        assert_eq!(part_two_functional(input.as_bytes()), Some(45000));
+        assert_eq!(part_two_imperative(input.as_bytes()), Some(45000));
+    }
+
+    #[test]
+    fn test_calories() {
+        let input = "1\r\n2\r\n\r\n\r\n10\n\n4\n5";
+        assert_eq!(calories(input.as_bytes(), 2), (10, 19));
+        assert_eq!(calories(input.as_bytes(), 5), (10, 22));
+        assert_eq!(calories("".as_bytes(), 3), (0, 0));
     }
 }
@@ -1,14 +1,4 @@
-use std::collections::HashMap;
-
-fn letter_score(x: char) -> usize {
-    *('a'..='z')
-        .chain('A'..='Z')
-        .enumerate()
-        .map(|(i, letter)| (letter, i + 1))
-        .collect::<HashMap<char, usize>>()
-        .get(&x)
-        .unwrap()
-}
+use advent_of_code::helpers::{charset::priority, CharSet};
 
 pub fn part_one(input: &str) -> Option<u32> {
     Some(
@@ -16,13 +6,8 @@ pub fn part_one(input: &str) -> Option<u32> {
             .lines()
             .map(|line| line.split_at(line.len() / 2))
             .map(|(a, b)| {
-                let result = a
-                    .chars()
-                    .find_map(|ch| b.contains(ch).then_some(ch))
-                    .map(letter_score)
-                    .unwrap();
-
-                u32::try_from(result).unwrap()
+                let common = CharSet::from(a) & CharSet::from(b);
+                priority(common.first().unwrap())
             })
             .sum(),
     )
@@ -35,20 +20,19 @@ pub fn part_two(input: &str) -> Option<u32> {
             .collect::<Vec<_>>()
             .chunks(3)
             .map(|chunk| {
-                let result = chunk[0]
-                    .chars()
-                    .find_map(|ch| (chunk[1].contains(ch) && chunk[2].contains(ch)).then_some(ch))
-                    .map(letter_score)
+                let common = chunk
+                    .iter()
+                    .map(|&rucksack| CharSet::from(rucksack))
+                    .reduce(CharSet::intersection)
                     .unwrap();
-
-                u32::try_from(result).unwrap()
+                priority(common.first().unwrap())
             })
             .sum(),
     )
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 3);
+    let input = &advent_of_code::read_file("inputs", advent_of_code::day!(3));
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -59,13 +43,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 3);
+        let input = advent_of_code::read_file("examples", advent_of_code::day!(3));
         assert_eq!(part_one(&input), Some(157));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 3);
+        let input = advent_of_code::read_file("examples", advent_of_code::day!(3));
         assert_eq!(part_two(&input), Some(70));
     }
 }
@@ -1,4 +1,9 @@
+use advent_of_code::helpers::{
+    parse,
+    viz::{self, Frame},
+};
 use std::collections::VecDeque;
+
 struct Step {
     count: usize,
     from: usize,
@@ -16,7 +21,9 @@ impl Step {
 }
 
 fn proccess_input(input: &str) -> (Vec<VecDeque<char>>, Vec<Step>) {
-    let (stacks, moves) = input.split_once("\n\n").unwrap();
+    let [stacks, moves] = parse::blocks(input)[..] else {
+        panic!("expected stacks and moves separated by a blank line");
+    };
 
     let rows: Vec<_> = stacks
         .lines()
@@ -45,18 +52,10 @@ fn proccess_input(input: &str) -> (Vec<VecDeque<char>>, Vec<Step>) {
         }
     }
 
-    let steps: Vec<Step> = moves
-        .lines()
-        .map(|line| {
-            let [count, from, to]: [usize; 3] = line
-                .split(char::is_alphabetic)
-                .filter(|x| !x.is_empty())
-                .map(|x| x.trim().parse::<usize>().unwrap())
-                .collect::<Vec<usize>>()
-                .try_into()
-                .unwrap();
-            Step::new(count, from, to)
-        })
+    let steps: Vec<Step> = parse::lines(moves, "move {} from {} to {}")
+        .unwrap()
+        .into_iter()
+        .map(|(count, from, to)| Step::new(count, from, to))
         .collect();
 
     (stacks, steps)
@@ -74,6 +73,23 @@ fn extract_output(stacks: Vec<VecDeque<char>>) -> Option<String> {
     Some(output)
 }
 
+/// The stacks as drawn in the puzzle, with the top crates in the first line.
+fn stacks_frame(stacks: &[VecDeque<char>]) -> Frame {
+    let height = stacks.iter().map(VecDeque::len).max().unwrap_or_default();
+    let lines: Vec<String> = (0..height)
+        .map(|row| {
+            stacks
+                .iter()
+                .map(|stack| match row.checked_sub(height - stack.len()) {
+                    Some(i) => format!("[{}] ", stack[i]),
+                    None => "    ".into(),
+                })
+                .collect()
+        })
+        .collect();
+    Frame::text(&lines.join("\n"))
+}
+
 pub fn part_one(input: &str) -> Option<String> {
     let (mut stacks, steps) = proccess_input(input);
 
@@ -81,6 +97,7 @@ pub fn part_one(input: &str) -> Option<String> {
         for _ in 0..step.count {
             let moving_crate = stacks.get_mut(step.from).unwrap().pop_front().unwrap();
             stacks.get_mut(step.to).unwrap().push_front(moving_crate);
+            viz::emit(|| stacks_frame(&stacks));
         }
     }
 
@@ -100,13 +117,14 @@ pub fn part_two(input: &str) -> Option<String> {
         let initial_items = stack.split_off(0);
         stack.extend(items);
         stack.extend(initial_items);
+        viz::emit(|| stacks_frame(&stacks));
     }
 
     extract_output(stacks)
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 5);
+    let input = &advent_of_code::read_file("inputs", advent_of_code::day!(5));
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -117,13 +135,23 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 5);
+        let input = advent_of_code::read_file("examples", advent_of_code::day!(5));
         assert_eq!(part_one(&input), Some("CMZ".to_string()));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 5);
+        let input = advent_of_code::read_file("examples", advent_of_code::day!(5));
         assert_eq!(part_two(&input), Some("MCD".to_string()));
     }
+
+    #[test]
+    fn test_stacks_frame() {
+        let input = advent_of_code::read_file("examples", advent_of_code::day!(5));
+        let (stacks, _) = proccess_input(&input);
+        assert_eq!(
+            stacks_frame(&stacks),
+            Frame::text("    [D]     \n[N] [C]     \n[Z] [M] [P] ")
+        );
+    }
 }
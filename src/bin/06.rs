@@ -1,11 +1,22 @@
-use std::collections::HashSet;
+use advent_of_code::helpers::CharSet;
 
 fn calc_position(input: &str, window_size: usize) -> Option<usize> {
-    input
-        .as_bytes()
-        .windows(window_size)
-        .position(|x| x.iter().collect::<HashSet<_>>().len() == window_size)
-        .map(|x| x + window_size)
+    let bytes = input.as_bytes();
+    // each character is toggled when it enters and leaves the window, so a duplicate clears its bit.
+    // the set only holds `window_size` characters when all of them are different.
+    let mut window = CharSet::new();
+
+    for (i, &byte) in bytes.iter().enumerate() {
+        window.toggle(char::from(byte));
+        if i >= window_size {
+            window.toggle(char::from(bytes[i - window_size]));
+        }
+        if window.len() == window_size {
+            return Some(i + 1);
+        }
+    }
+
+    None
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
@@ -19,7 +30,7 @@ pub fn part_two(input: &str) -> Option<usize> {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 6);
+    let input = &advent_of_code::read_file("inputs", advent_of_code::day!(6));
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -30,13 +41,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 6);
+        let input = advent_of_code::read_file("examples", advent_of_code::day!(6));
         assert_eq!(part_one(&input), Some(11));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 6);
+        let input = advent_of_code::read_file("examples", advent_of_code::day!(6));
         assert_eq!(part_two(&input), Some(26));
     }
 }
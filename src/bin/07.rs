@@ -16,10 +16,12 @@ enum Cd<'a> {
 #[derive(Debug, Clone)]
 enum DataType<'a> {
     File(u32),
+    // the name is only read by the `Debug` output.
+    #[allow(dead_code)]
     Dir(&'a str),
 }
 
-fn ls(input: &str) -> Command {
+fn ls(input: &str) -> Command<'_> {
     let command = input
         .trim()
         .lines()
@@ -42,7 +44,7 @@ fn ls(input: &str) -> Command {
     Command::Ls(command)
 }
 
-fn cd(input: &str) -> Command {
+fn cd(input: &str) -> Command<'_> {
     let (_, dir) = input.trim().split_once("cd ").unwrap();
     match dir {
         "/" => Command::Cd(Cd::Root),
@@ -51,7 +53,7 @@ fn cd(input: &str) -> Command {
     }
 }
 
-fn parse_commands(input: &str) -> Vec<Command> {
+fn parse_commands(input: &str) -> Vec<Command<'_>> {
     input
         .trim()
         .split("$ ")
@@ -146,7 +148,7 @@ pub fn part_two(input: &str) -> Option<u32> {
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 7);
+    let input = &advent_of_code::read_file("inputs", advent_of_code::day!(7));
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -157,13 +159,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 7);
+        let input = advent_of_code::read_file("examples", advent_of_code::day!(7));
         assert_eq!(part_one(&input), Some(95437));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 7);
+        let input = advent_of_code::read_file("examples", advent_of_code::day!(7));
         assert_eq!(part_two(&input), Some(24933642));
     }
 }
@@ -1,107 +1,53 @@
-use std::ops::Range;
+use advent_of_code::helpers::{grid::DIRECTIONS_4, Grid};
 
-struct Grid {
-    data: Vec<u32>,
-    size: usize,
+fn parse(input: &str) -> Grid<u32> {
+    Grid::parse(input, |ch| ch.to_digit(10).unwrap())
 }
 
-impl Grid {
-    fn get_cell(&self, col: usize, row: usize) -> u32 {
-        self.data[col + self.size * row]
+/// Number of trees seen from `(x, y)` in `direction`, up to and including the first one that blocks the view.
+fn viewing_distance(grid: &Grid<u32>, x: usize, y: usize, direction: (isize, isize)) -> usize {
+    let height = grid[(x, y)];
+    let mut distance = 0;
+    for (_, tree) in grid.ray(x, y, direction) {
+        distance += 1;
+        if *tree >= height {
+            break;
+        }
     }
-
-    fn get_row(&self, row: usize) -> Vec<u32> {
-        self.data[(row * self.size)..((row + 1) * self.size)].to_vec()
-    }
-
-    fn get_col(&self, col: usize) -> Vec<u32> {
-        (0..self.size)
-            .map(|i| self.data[i * self.size + col])
-            .collect()
-    }
-
-    fn get_from_directions(&self, col: usize, row: usize) -> [Vec<u32>; 4] {
-        let full_row = self.get_row(row);
-        let full_col = self.get_col(col);
-
-        let (left, right) = full_row.split_at(col);
-        let (top, bottom) = full_col.split_at(row);
-
-        // seen from perspective of the current element, that's why reversed
-        let left: Vec<u32> = left.iter().copied().rev().collect();
-        let top: Vec<u32> = top.iter().copied().rev().collect();
-        // without the current element itself
-        let right = right[1..].to_vec();
-        let bottom = bottom[1..].to_vec();
-
-        [left, right, top, bottom]
-    }
-}
-
-fn create_grid(input: &str) -> Vec<u32> {
-    input
-        .lines()
-        .flat_map(|line| line.chars().map(|ch| ch.to_digit(10).unwrap()))
-        .collect()
+    distance
 }
 
-fn cartesian_product(range: Range<usize>) -> Vec<(usize, usize)> {
-    range
-        .clone()
-        .flat_map(|row| range.clone().map(move |col| (col, row)))
-        .collect()
-}
-
-// After refactoring both solutions became >7x slower but the code is much more readable and concise.
 pub fn part_one(input: &str) -> Option<usize> {
-    let grid = Grid {
-        size: input.lines().next().unwrap().len(),
-        data: create_grid(input),
-    };
-
-    let edges_count = (grid.size - 1) * 4;
+    let grid = parse(input);
 
-    let interior_count = cartesian_product(1..grid.size - 1)
-        .into_iter()
-        .map(|(col, row)| {
-            let height = grid.get_cell(col, row);
-            grid.get_from_directions(col, row)
+    // trees on the edge have an empty ray and are always visible.
+    let visible = grid
+        .iter()
+        .filter(|&((x, y), height)| {
+            DIRECTIONS_4
                 .iter()
-                .map(|direction| direction.iter().all(|h| *h < height))
-                .any(|visible| visible)
+                .any(|&direction| grid.ray(x, y, direction).all(|(_, tree)| tree < height))
         })
-        .filter(|visible| *visible)
         .count();
 
-    Some(edges_count + interior_count)
+    Some(visible)
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let grid = Grid {
-        size: input.lines().next().unwrap().len(),
-        data: create_grid(input),
-    };
+    let grid = parse(input);
 
-    cartesian_product(1..grid.size - 1)
-        .into_iter()
-        .map(|(col, row)| {
-            let height = grid.get_cell(col, row);
-            grid.get_from_directions(col, row)
+    grid.positions()
+        .map(|(x, y)| {
+            DIRECTIONS_4
                 .iter()
-                .map(|direction| {
-                    direction
-                        .iter()
-                        .position(|h| *h >= height)
-                        .map(|count| count + 1)
-                        .unwrap_or_else(|| direction.len())
-                })
+                .map(|&direction| viewing_distance(&grid, x, y, direction))
                 .product()
         })
         .max()
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 8);
+    let input = &advent_of_code::read_file("inputs", advent_of_code::day!(8));
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -112,13 +58,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 8);
+        let input = advent_of_code::read_file("examples", advent_of_code::day!(8));
         assert_eq!(part_one(&input), Some(21));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 8);
+        let input = advent_of_code::read_file("examples", advent_of_code::day!(8));
         assert_eq!(part_two(&input), Some(8));
     }
 }
@@ -1,100 +1,83 @@
-use std::collections::HashSet;
-
-#[derive(Eq, PartialEq, Hash, Clone, Copy, Debug)]
-struct Position {
-    x: i32,
-    y: i32,
+use advent_of_code::helpers::viz::{self, Frame};
+use advent_of_code::helpers::{Direction, Point, SparseGrid};
+
+fn parse(input: &str) -> impl Iterator<Item = (Direction, usize)> + '_ {
+    input.lines().map(|line| {
+        let (direction, steps) = line.split_once(' ').unwrap();
+        (direction.parse().unwrap(), steps.parse().unwrap())
+    })
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let start = Position { x: 0, y: 0 };
-    let mut seen_pos = HashSet::new();
-
-    let mut head = start;
-    let mut tail = start;
-
-    seen_pos.insert(start);
+/// Moves the head of a rope with `length` knots and returns the positions visited by its tail.
+fn simulate(input: &str, length: usize) -> SparseGrid<()> {
+    let mut knots = vec![Point::ORIGIN; length];
+    let mut seen_pos = SparseGrid::from_iter([(Point::ORIGIN, ())]);
 
-    for line in input.lines() {
-        let (dir, steps) = line.split_once(' ').unwrap();
-        let steps = steps.parse().unwrap();
+    for (direction, steps) in parse(input) {
         for _ in 0..steps {
-            match dir {
-                "U" => head.y -= 1,
-                "D" => head.y += 1,
-                "L" => head.x -= 1,
-                "R" => head.x += 1,
-                _ => panic!("invalid direction"),
-            }
-
-            let current_gap = Position {
-                x: head.x - tail.x,
-                y: head.y - tail.y,
-            };
+            knots[0] += direction;
 
-            let should_catch_up = current_gap.x.abs() > 1 || current_gap.y.abs() > 1;
+            for i in 1..knots.len() {
+                let head = knots[i - 1];
+                let tail = &mut knots[i];
 
-            if should_catch_up {
-                // signum converts positive numbers to 1, negative to -1
-                tail.x += current_gap.x.signum();
-                tail.y += current_gap.y.signum();
-                seen_pos.insert(tail);
+                if head.chebyshev(*tail) > 1 {
+                    *tail += (head - *tail).signum();
+                }
             }
+
+            seen_pos.insert(knots[length - 1], ());
+            viz::emit(|| rope_frame(&knots, &seen_pos));
         }
     }
 
-    Some(seen_pos.len())
+    seen_pos
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let start = Position { x: 0, y: 0 };
-    let mut seen_pos = HashSet::new();
-
-    let mut knots = [start; 10];
-    seen_pos.insert(start);
+/// The rope on top of the trail of its tail. The head is `H`, the other knots are numbered.
+fn rope_frame(knots: &[Point], trail: &SparseGrid<()>) -> Frame {
+    let mut grid: SparseGrid<char> = trail.positions().map(|position| (position, '#')).collect();
+    for (i, knot) in knots.iter().enumerate().rev() {
+        let ch = match i {
+            0 => 'H',
+            i => char::from_digit(i as u32 % 10, 10).unwrap(),
+        };
+        grid.insert(*knot, ch);
+    }
+    Frame::text(&grid.render('.', |_, ch| *ch))
+}
 
-    for line in input.lines() {
-        let (dir, steps) = line.split_once(' ').unwrap();
-        let steps = steps.parse().unwrap();
-        for _ in 0..steps {
-            match dir {
-                "U" => knots[0].y -= 1,
-                "D" => knots[0].y += 1,
-                "L" => knots[0].x -= 1,
-                "R" => knots[0].x += 1,
-                _ => panic!("invalid direction"),
+/// Draws the positions visited by the tail, with `s` marking the start.
+fn render_trail(trail: &SparseGrid<()>) -> String {
+    trail.render(
+        '.',
+        |position, _| {
+            if position == Point::ORIGIN {
+                's'
+            } else {
+                '#'
             }
+        },
+    )
+}
 
-            for i in 1..knots.len() {
-                let head = knots[i - 1];
-                let mut tail = &mut knots[i];
-
-                let current_gap = Position {
-                    x: head.x - tail.x,
-                    y: head.y - tail.y,
-                };
-
-                let should_catch_up = current_gap.x.abs() > 1 || current_gap.y.abs() > 1;
-
-                if should_catch_up {
-                    // signum converts positive numbers to 1, negative to -1
-                    tail.x += current_gap.x.signum();
-                    tail.y += current_gap.y.signum();
-                    if i == 9 {
-                        seen_pos.insert(*tail);
-                    }
-                }
-            }
-        }
-    }
+pub fn part_one(input: &str) -> Option<usize> {
+    Some(simulate(input, 2).len())
+}
 
-    Some(seen_pos.len())
+pub fn part_two(input: &str) -> Option<usize> {
+    Some(simulate(input, 10).len())
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 9);
+    let input = &advent_of_code::read_file("inputs", advent_of_code::day!(9));
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
+
+    // `cargo solve 09 -- --trail` also prints the positions visited by the tail in part two.
+    if pico_args::Arguments::from_env().contains("--trail") {
+        println!("{}", render_trail(&simulate(input, 10)));
+    }
 }
 
 #[cfg(test)]
@@ -103,13 +86,22 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 9);
+        let input = advent_of_code::read_file("examples", advent_of_code::day!(9));
         assert_eq!(part_one(&input), Some(13));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 9);
+        let input = advent_of_code::read_file("examples", advent_of_code::day!(9));
         assert_eq!(part_two(&input), Some(1));
     }
+
+    #[test]
+    fn test_render_trail() {
+        let input = advent_of_code::read_file("examples", advent_of_code::day!(9));
+        assert_eq!(
+            render_trail(&simulate(&input, 2)),
+            "..##.\n...##\n.####\n....#\ns###."
+        );
+    }
 }
@@ -1,3 +1,5 @@
+use advent_of_code::helpers::viz::{self, Frame};
+
 #[derive(Debug, PartialEq)]
 enum Signal {
     Noop,
@@ -39,12 +41,32 @@ pub fn part_one(input: &str) -> Option<i16> {
     Some(total)
 }
 
+const WIDTH: usize = 40;
+const HEIGHT: usize = 6;
+
+/// Draws the screen, leaving the pixels the beam has not reached yet blank.
+fn render_screen(pixels: &[bool], drawn: usize) -> String {
+    pixels
+        .chunks(WIDTH)
+        .enumerate()
+        .map(|(row, chunk)| {
+            chunk
+                .iter()
+                .enumerate()
+                .map(|(col, &lit)| match (row * WIDTH + col < drawn, lit) {
+                    (false, _) => ' ',
+                    (true, true) => '#',
+                    (true, false) => '.',
+                })
+                .collect()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
 pub fn part_two(input: &str) -> Option<String> {
     let signals = parse(input);
 
-    const WIDTH: usize = 40;
-    const HEIGHT: usize = 6;
-
     let mut x = 1;
     let mut sprite_pos = 0..=2;
     let mut cycle = 1;
@@ -53,26 +75,22 @@ pub fn part_two(input: &str) -> Option<String> {
     signals.iter().for_each(|signal| {
         sprite_pos = x - 1..=x + 1;
         result[cycle - 1] = sprite_pos.contains(&((cycle as i16 - 1) % WIDTH as i16));
+        viz::emit(|| Frame::text(&render_screen(&result, cycle)));
         cycle += 1;
 
         if let Signal::Addx(num) = signal {
             result[cycle - 1] = sprite_pos.contains(&((cycle as i16 - 1) % WIDTH as i16));
+            viz::emit(|| Frame::text(&render_screen(&result, cycle)));
             cycle += 1;
             x += num;
         }
     });
 
-    let screen = result
-        .chunks(WIDTH)
-        .map(|chunk| chunk.iter().map(|&x| if x { '#' } else { '.' }).collect())
-        .collect::<Vec<String>>()
-        .join("\n");
-
-    Some(screen)
+    Some(render_screen(&result, result.len()))
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 10);
+    let input = &advent_of_code::read_file("inputs", advent_of_code::day!(10));
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -83,13 +101,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 10);
+        let input = advent_of_code::read_file("examples", advent_of_code::day!(10));
         assert_eq!(part_one(&input), Some(13140));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 10);
+        let input = advent_of_code::read_file("examples", advent_of_code::day!(10));
         assert_eq!(
             part_two(&input),
             Some(String::from(
@@ -1,9 +1,20 @@
+use advent_of_code::helpers::{cycle, math, parse, parse::Fields, IterExt};
+
+const MONKEY_PATTERN: &str = "Monkey {}:
+  Starting items: {}
+  Operation: new = old {} {}
+  Test: divisible by {}
+    If true: throw to monkey {}
+    If false: throw to monkey {}";
+
 #[derive(Debug, Clone)]
-struct Monkey<'a> {
+struct Monkey {
     id: u64,
     items: Vec<u64>,
     inspections_count: u64,
-    operation: Vec<&'a str>,
+    operator: char,
+    /// `None` if the operand is `old`.
+    operand: Option<u64>,
     test: TestCase,
 }
 
@@ -14,36 +25,32 @@ struct TestCase {
     false_target: u64,
 }
 
-impl<'a> Monkey<'a> {
-    fn new(id: u64, items: Vec<u64>, operation: Vec<&'a str>, test: TestCase) -> Self {
-        Self {
-            id,
-            items,
-            inspections_count: 0,
-            operation,
-            test,
-        }
-    }
-
+impl Monkey {
     fn make_inspection(&mut self) -> u64 {
         self.items.reverse();
         let lh = self.items.pop().unwrap();
-
-        let rh = if self.operation[2] == "old" {
-            lh
-        } else {
-            self.operation[2].parse::<u64>().unwrap()
-        };
+        let rh = self.operand.unwrap_or(lh);
 
         self.increase_inspection_count();
+        self.apply_operation(lh, rh)
+    }
 
-        match self.operation[1] {
-            "*" => lh * rh,
-            "+" => lh + rh,
+    fn apply_operation(&self, lh: u64, rh: u64) -> u64 {
+        match self.operator {
+            '*' => lh * rh,
+            '+' => lh + rh,
             _ => panic!("not recognized operator sign"),
         }
     }
 
+    fn target(&self, worry_level: u64) -> u64 {
+        if worry_level % self.test.divisor == 0 {
+            self.test.true_target
+        } else {
+            self.test.false_target
+        }
+    }
+
     fn increase_inspection_count(&mut self) {
         self.inspections_count += 1;
     }
@@ -53,80 +60,34 @@ impl<'a> Monkey<'a> {
     }
 }
 
-fn parse(input: &str) -> Vec<Monkey> {
-    input
-        .split("\n\n")
-        .map(|monkey| {
-            let mut lines = monkey.lines();
-            let id = lines
-                .next()
-                .unwrap()
-                .trim()
-                .strip_prefix("Monkey ")
-                .unwrap()
-                .strip_suffix(':')
-                .unwrap()
-                .parse::<u64>()
-                .unwrap();
-
-            let items = lines
-                .next()
-                .unwrap()
-                .trim()
-                .strip_prefix("Starting items: ")
-                .unwrap()
-                .split(',')
-                .map(|ch| ch.trim().parse::<u64>().unwrap())
-                .collect::<Vec<_>>();
-
-            let operation = lines
-                .next()
-                .unwrap()
-                .trim()
-                .strip_prefix("Operation: new = ")
-                .unwrap()
-                .split_whitespace()
-                .collect::<Vec<&str>>();
-
-            let divisor = lines
-                .next()
-                .unwrap()
-                .trim()
-                .strip_prefix("Test: divisible by ")
-                .unwrap()
-                .parse::<u64>()
-                .unwrap();
-
-            let true_target = lines
-                .next()
-                .unwrap()
-                .trim()
-                .strip_prefix("If true: throw to monkey ")
-                .unwrap()
-                .parse::<u64>()
-                .unwrap();
-
-            let false_target = lines
-                .next()
-                .unwrap()
-                .trim()
-                .strip_prefix("If false: throw to monkey ")
-                .unwrap()
-                .parse::<u64>()
-                .unwrap();
-
-            Monkey::new(
-                id,
-                items,
-                operation,
-                TestCase {
-                    divisor,
-                    true_target,
-                    false_target,
-                },
-            )
+/// Builds a monkey from the fields of `MONKEY_PATTERN`.
+impl Fields for Monkey {
+    const COUNT: usize = 7;
+
+    fn from_fields(fields: &[&str]) -> Result<Self, usize> {
+        let (id, items, operator, operand, divisor, true_target, false_target) =
+            <(u64, String, char, String, u64, u64, u64)>::from_fields(fields)?;
+
+        Ok(Monkey {
+            id,
+            items: parse::ints(&items).collect(),
+            inspections_count: 0,
+            operator,
+            operand: operand.parse().ok(),
+            test: TestCase {
+                divisor,
+                true_target,
+                false_target,
+            },
         })
-        .collect::<Vec<_>>()
+    }
+}
+
+fn parse(input: &str) -> Vec<Monkey> {
+    parse::blocks(input)
+        .into_iter()
+        .map(|block| parse::fields(block, MONKEY_PATTERN).unwrap())
+        .collect()
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
@@ -158,62 +119,79 @@ pub fn part_one(input: &str) -> Option<u64> {
         }
     }
 
-    let mut results = monkeys
+    let monkey_businnes = monkeys
         .iter()
         .map(|monkey| monkey.inspections_count)
-        .collect::<Vec<u64>>();
-
-    results.sort();
-
-    let monkey_businnes = results.last().unwrap() * results.get(results.len() - 2).unwrap();
+        .top_k(2)
+        .iter()
+        .product();
 
     Some(monkey_businnes)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let mut monkeys = parse(input);
-    let common_multiple: u64 = monkeys.iter().map(|monkey| monkey.test.divisor).product();
-    for _ in 0..10_000 {
-        for monkey in 0..monkeys.len() {
-            for _ in 0..monkeys[monkey].items.len() {
-                let worry_level = monkeys[monkey].make_inspection() % common_multiple;
-
-                let TestCase {
-                    divisor,
-                    true_target,
-                    false_target,
-                } = monkeys[monkey].test;
+/// A single item. Items never affect each other, so each one can be followed on its own.
+#[derive(Debug, Clone)]
+struct Item {
+    monkey: usize,
+    worry_level: u64,
+    /// Inspections of this item by each monkey.
+    inspections: Vec<u64>,
+}
 
-                let target_monkey = monkeys
-                    .iter_mut()
-                    .find(|target| {
-                        let source = if worry_level % divisor == 0 {
-                            true_target
-                        } else {
-                            false_target
-                        };
-                        target.id == source
-                    })
-                    .unwrap();
-                target_monkey.add_item(worry_level);
+impl Item {
+    /// Moves the item through one round. It is inspected again in the same round
+    /// whenever it is thrown to a monkey that has not had its turn yet.
+    fn round(&mut self, monkeys: &[Monkey], common_multiple: u64) {
+        loop {
+            let monkey = &monkeys[self.monkey];
+            self.inspections[self.monkey] += 1;
+
+            let rh = monkey.operand.unwrap_or(self.worry_level);
+            self.worry_level = monkey.apply_operation(self.worry_level, rh) % common_multiple;
+
+            let target = monkey.target(self.worry_level);
+            let next = monkeys.iter().position(|m| m.id == target).unwrap();
+            let round_over = next <= self.monkey;
+            self.monkey = next;
+            if round_over {
+                break;
             }
         }
     }
+}
 
-    let mut results = monkeys
-        .iter()
-        .map(|monkey| monkey.inspections_count)
-        .collect::<Vec<u64>>();
-
-    results.sort_unstable();
+pub fn part_two(input: &str) -> Option<u64> {
+    let monkeys = parse(input);
+    // worry levels only matter modulo each divisor, so they can be kept below their lcm.
+    let common_multiple = math::lcm_of(monkeys.iter().map(|monkey| monkey.test.divisor));
+    let mut results = vec![0; monkeys.len()];
+
+    for (monkey, start) in monkeys.iter().enumerate() {
+        for &worry_level in &start.items {
+            let item = Item {
+                monkey,
+                worry_level,
+                inspections: vec![0; monkeys.len()],
+            };
+            // the item soon repeats a position and worry level, after that its inspections can be extrapolated.
+            let cycle = cycle::find_cycle(
+                item,
+                |item| item.round(&monkeys, common_multiple),
+                |item| (item.monkey, item.worry_level),
+            );
+            for (i, result) in results.iter_mut().enumerate() {
+                *result += cycle.extrapolate(10_000, |item| item.inspections[i] as i64) as u64;
+            }
+        }
+    }
 
-    let monkey_businnes = results.iter().rev().take(2).product();
+    let monkey_businnes = results.into_iter().top_k(2).iter().product();
 
     Some(monkey_businnes)
 }
 
 fn main() {
-    let input = &advent_of_code::read_file("inputs", 11);
+    let input = &advent_of_code::read_file("inputs", advent_of_code::day!(11));
     advent_of_code::solve!(1, part_one, input);
     advent_of_code::solve!(2, part_two, input);
 }
@@ -224,13 +202,19 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = advent_of_code::read_file("examples", 11);
+        let input = advent_of_code::read_file("examples", advent_of_code::day!(11));
         assert_eq!(part_one(&input), Some(10605));
     }
 
     #[test]
     fn test_part_two() {
-        let input = advent_of_code::read_file("examples", 11);
+        let input = advent_of_code::read_file("examples", advent_of_code::day!(11));
         assert_eq!(part_two(&input), Some(2713310158));
     }
+
+    #[test]
+    fn test_parse_crlf() {
+        let input = advent_of_code::read_file("examples", advent_of_code::day!(11));
+        assert_eq!(part_one(&input.replace('\n', "\r\n")), Some(10605));
+    }
 }
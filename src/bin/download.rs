@@ -2,19 +2,35 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use advent_of_code::aoc_cli;
-use std::process;
+use advent_of_code::{
+    aoc_cli, calendar,
+    config::Config,
+    unlock::{self, Clock, SystemClock, ThreadSleeper},
+    Day,
+};
+use std::{io::Write, process, time::Duration};
+
+/// Seconds to wait after the unlock before the first download attempt.
+const UNLOCK_GRACE: u64 = 5;
+/// Download attempts after the unlock, with a delay of 2s, 4s, 8s... in between.
+const UNLOCK_ATTEMPTS: u32 = 6;
 
 struct Args {
-    day: u8,
+    day: Day,
     year: Option<u16>,
+    force: bool,
+    wait: bool,
 }
 
 fn parse_args() -> Result<Args, pico_args::Error> {
     let mut args = pico_args::Arguments::from_env();
     Ok(Args {
+        force: args.contains(["-f", "--force"]),
+        wait: args.contains(["-w", "--wait"]),
+        year: args
+            .opt_value_from_str(["-y", "--year"])?
+            .or(Config::get().year),
         day: args.free_from_str()?,
-        year: args.opt_value_from_str(["-y", "--year"])?,
     })
 }
 
@@ -32,15 +48,48 @@ fn main() {
         process::exit(1);
     }
 
-    match aoc_cli::download(args.day, args.year) {
-        Ok(cmd_output) => {
-            if !cmd_output.status.success() {
-                process::exit(1);
-            }
-        }
+    let result = if args.wait {
+        wait_and_download(&args)
+    } else {
+        aoc_cli::download(args.day, args.year, args.force)
+    };
+
+    match result {
+        Ok(_) => {}
+        Err(aoc_cli::AocCliError::BadExitStatus(_)) => process::exit(1),
         Err(e) => {
             eprintln!("failed to spawn aoc-cli: {e}");
             process::exit(1);
         }
     }
 }
+
+/// Counts down to the puzzle unlock, then downloads with retries in case the input is not available yet.
+fn wait_and_download(args: &Args) -> Result<(), aoc_cli::AocCliError> {
+    let clock = SystemClock;
+    let year = args
+        .year
+        .unwrap_or_else(|| calendar::current_year(clock.now()));
+    let unlock = calendar::unlock_time(year, args.day);
+
+    if clock.now() < unlock {
+        unlock::wait_until(unlock, UNLOCK_GRACE, &clock, &ThreadSleeper, |left| {
+            print!(
+                "\r⏳ Day {} of {} unlocks in {} ",
+                args.day.into_inner(),
+                year,
+                unlock::format_countdown(left)
+            );
+            std::io::stdout().flush().ok();
+        });
+        println!();
+    }
+
+    unlock::retry_with_backoff(
+        UNLOCK_ATTEMPTS,
+        Duration::from_secs(2),
+        &ThreadSleeper,
+        || aoc_cli::download(args.day, Some(year), args.force),
+        |e, delay| println!("Download failed ({e}), retrying in {}s...", delay.as_secs()),
+    )
+}
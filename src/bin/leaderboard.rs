@@ -0,0 +1,56 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::{calendar, config::Config, leaderboard};
+use std::process;
+
+struct Args {
+    id: Option<u64>,
+    year: Option<u16>,
+    file: Option<String>,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        year: args.opt_value_from_str(["-y", "--year"])?,
+        file: args.opt_value_from_str(["-f", "--file"])?,
+        id: args.opt_free_from_str()?,
+    })
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {e}");
+            process::exit(1);
+        }
+    };
+
+    let json = match (&args.file, args.id) {
+        (Some(file), _) => leaderboard::read_local(file),
+        (None, Some(id)) => {
+            let year = args
+                .year
+                .or(Config::get().year)
+                .unwrap_or_else(|| calendar::latest_event_year(calendar::unix_now()));
+            leaderboard::fetch(id, year)
+        }
+        (None, None) => {
+            eprintln!(
+                "Need to specify a leaderboard id or a file. example: `cargo leaderboard 123456`"
+            );
+            process::exit(1);
+        }
+    };
+
+    match json.and_then(|json| leaderboard::parse(&json)) {
+        Ok(leaderboard) => print!("{}", leaderboard::render(&leaderboard)),
+        Err(e) => {
+            eprintln!("Failed to load leaderboard: {e}");
+            process::exit(1);
+        }
+    }
+}
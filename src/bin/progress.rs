@@ -0,0 +1,82 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::{
+    calendar,
+    config::Config,
+    progress::{self, Progress},
+    runner::{self, Outcome},
+};
+use std::{fs, process};
+
+struct Args {
+    readme: bool,
+    year: Option<u16>,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    Ok(Args {
+        readme: args.contains(["-r", "--readme"]),
+        year: args
+            .opt_value_from_str(["-y", "--year"])?
+            .or(Config::get().year),
+    })
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {e}");
+            process::exit(1);
+        }
+    };
+
+    let config = Config::get();
+    let year = args
+        .year
+        .unwrap_or_else(|| calendar::latest_event_year(calendar::unix_now()));
+    let release = config.runner.release.unwrap_or(cfg!(not(debug_assertions)));
+
+    // a part counts as solved if it returns `Some` for the real input.
+    let days = runner::solution_days();
+    if !runner::build_solutions(&days, release) {
+        eprintln!("Failed to build solutions.");
+        process::exit(1);
+    }
+
+    let mut progress = Progress::default();
+    for day in days {
+        if let Outcome::Solved(output) = runner::run_solution(day, release, config.runner.timeout) {
+            progress.days[day.index()] = progress::parse_solved(&output);
+        }
+    }
+
+    print!("{}", progress::render_calendar(&progress, year));
+
+    if args.readme {
+        let readme_path = config.root.join("README.md");
+        let table = progress::render_readme_table(&progress, year);
+
+        let updated = fs::read_to_string(&readme_path)
+            .ok()
+            .and_then(|readme| progress::update_readme(&readme, &table));
+
+        match updated.map(|readme| fs::write(&readme_path, readme)) {
+            Some(Ok(_)) => println!("🎄 Updated star table in \"README.md\"."),
+            Some(Err(e)) => {
+                eprintln!("Failed to write \"README.md\": {e}");
+                process::exit(1);
+            }
+            None => {
+                eprintln!(
+                    "Could not find the star table in \"README.md\". It must be wrapped in \"{}\" markers.",
+                    progress::README_MARKER
+                );
+                process::exit(1);
+            }
+        }
+    }
+}
@@ -2,11 +2,11 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use advent_of_code::aoc_cli;
+use advent_of_code::{aoc_cli, config::Config, Day};
 use std::process;
 
 struct Args {
-    day: u8,
+    day: Day,
     year: Option<u16>,
 }
 
@@ -14,7 +14,9 @@ fn parse_args() -> Result<Args, pico_args::Error> {
     let mut args = pico_args::Arguments::from_env();
     Ok(Args {
         day: args.free_from_str()?,
-        year: args.opt_value_from_str(["-y", "--year"])?,
+        year: args
+            .opt_value_from_str(["-y", "--year"])?
+            .or(Config::get().year),
     })
 }
 
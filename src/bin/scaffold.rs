@@ -2,113 +2,218 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
+use advent_of_code::{
+    aoc_cli, calendar,
+    config::Config,
+    scaffold::{self, DayPaths, Step, TemplateContext},
+    vault, Day,
+};
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-const MODULE_TEMPLATE: &str = r###"pub fn part_one(input: &str) -> Option<u32> {
-    None
-}
-
-pub fn part_two(input: &str) -> Option<u32> {
-    None
-}
-
-fn main() {
-    let input = &advent_of_code::read_file("inputs", DAY);
-    advent_of_code::solve!(1, part_one, input);
-    advent_of_code::solve!(2, part_two, input);
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let input = advent_of_code::read_file("examples", DAY);
-        assert_eq!(part_one(&input), None);
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = advent_of_code::read_file("examples", DAY);
-        assert_eq!(part_two(&input), None);
-    }
+struct Args {
+    days: Vec<Day>,
+    year: Option<u16>,
+    template: String,
+    download: bool,
+    force: bool,
+    dry_run: bool,
 }
-"###;
 
-fn parse_args() -> Result<u8, pico_args::Error> {
+fn parse_args() -> Result<Args, pico_args::Error> {
     let mut args = pico_args::Arguments::from_env();
-    args.free_from_str()
+    Ok(Args {
+        year: args
+            .opt_value_from_str(["-y", "--year"])?
+            .or(Config::get().year),
+        template: args
+            .opt_value_from_str(["-t", "--template"])?
+            .unwrap_or_else(|| scaffold::DEFAULT_TEMPLATE.into()),
+        download: args.contains(["-d", "--download"]),
+        force: args.contains(["-f", "--force"]),
+        dry_run: args.contains("--dry-run"),
+        days: args.free_from_fn(scaffold::parse_days)?,
+    })
 }
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
+fn safe_create_file(path: &Path) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+fn write_module(path: &Path, module: &str) -> Result<(), String> {
+    let mut file =
+        safe_create_file(path).map_err(|e| format!("Failed to create module file: {e}"))?;
+
+    file.write_all(module.as_bytes()).map_err(|e| {
+        // don't leave a half-written module behind, it would block the next attempt.
+        fs::remove_file(path).ok();
+        format!("Failed to write module contents: {e}")
+    })
 }
 
 fn main() {
-    let day = match parse_args() {
-        Ok(day) => day,
-        Err(_) => {
-            eprintln!("Need to specify a day (as integer). example: `cargo scaffold 7`");
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("{e}");
+            eprintln!("Need to specify a day or a range of days. example: `cargo scaffold 7` or `cargo scaffold 12..25`");
             process::exit(1);
         }
     };
 
-    let day_padded = format!("{day:02}");
-
-    let input_path = format!("src/inputs/{day_padded}.txt");
-    let example_path = format!("src/examples/{day_padded}.txt");
-    let module_path = format!("src/bin/{day_padded}.rs");
-
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
+    let year = args
+        .year
+        .unwrap_or_else(|| calendar::latest_event_year(calendar::unix_now()));
+
+    // render once upfront, so that a broken template does not leave a download behind.
+    let template = match scaffold::load_template(&args.template).and_then(|source| {
+        let context = TemplateContext {
+            day: args.days[0],
+            year,
+            title: String::new(),
+        };
+        scaffold::render(&source, &context).map(|_| source)
+    }) {
+        Ok(source) => source,
         Err(e) => {
-            eprintln!("Failed to create module file: {e}");
+            eprintln!("Failed to render template \"{}\": {e}", args.template);
             process::exit(1);
         }
     };
 
-    match file.write_all(MODULE_TEMPLATE.replace("DAY", &day.to_string()).as_bytes()) {
-        Ok(_) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
-        }
+    if args.download && !args.dry_run && aoc_cli::check().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        process::exit(1);
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+    let mut failed = false;
+
+    for &day in &args.days {
+        let paths = DayPaths::new(day);
+        let steps = scaffold::plan(&paths, args.download, args.force);
+
+        if args.days.len() > 1 || args.dry_run {
+            println!("Day {day}:");
         }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+
+        if args.dry_run {
+            for step in &steps {
+                println!("  would {step}");
+            }
+            continue;
         }
-    }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+        let context = TemplateContext {
+            day,
+            year,
+            title: format!("Day {}", day.into_inner()),
+        };
+
+        if let Err(e) = scaffold_day(&paths, &steps, &template, args.year, context) {
+            eprintln!("{e}");
+            failed = true;
         }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+    }
+
+    if failed {
+        process::exit(1);
+    }
+
+    if !args.dry_run {
+        println!("---");
+        println!(
+            "🎄 Type `cargo solve {}` to run your solution.",
+            args.days[0]
+        );
+    }
+}
+
+/// Runs the planned steps for a day. Stops at the first failure, so a failed download does not create a module.
+fn scaffold_day(
+    paths: &DayPaths,
+    steps: &[Step],
+    template: &str,
+    year: Option<u16>,
+    mut context: TemplateContext,
+) -> Result<(), String> {
+    let config = Config::get();
+    let mut puzzle = fs::read_to_string(&paths.puzzle).ok();
+
+    for step in steps {
+        match step {
+            Step::Download => {
+                let downloaded = aoc_cli::download(context.day, year, false);
+
+                // a throttled or failed download leaves no input behind, create an empty one to paste it into.
+                if !paths.input.exists() && !vault::sealed_path(&paths.input).exists() {
+                    safe_create_file(&paths.input)
+                        .map_err(|e| format!("Failed to create file: {e}"))?;
+                    println!(
+                        "Created empty file \"{}\"",
+                        config.display_path(&paths.input)
+                    );
+                }
+
+                downloaded.map_err(|e| match e {
+                    aoc_cli::AocCliError::BadExitStatus(_) => {
+                        "Download failed, no module was created.".to_string()
+                    }
+                    e => format!("failed to spawn aoc-cli: {e}"),
+                })?;
+                puzzle = fs::read_to_string(&paths.puzzle).ok();
+            }
+            Step::CreateModule(path) | Step::ReplaceModule(path, _) => {
+                // the title is known if the puzzle description was downloaded before.
+                if let Some(title) = puzzle.as_deref().and_then(scaffold::puzzle_title) {
+                    context.title = title;
+                }
+                let module = scaffold::render(template, &context)
+                    .map_err(|e| format!("Failed to render template: {e}"))?;
+
+                if let Step::ReplaceModule(_, backup) = step {
+                    fs::rename(path, backup)
+                        .map_err(|e| format!("Failed to back up module file: {e}"))?;
+                    println!(
+                        "Backed up module file to \"{}\"",
+                        config.display_path(backup)
+                    );
+                }
+
+                write_module(path, &module)?;
+                println!("Created module file \"{}\"", config.display_path(path));
+            }
+            Step::SkipModule(path) => {
+                println!(
+                    "Module file \"{}\" already exists, skipping. Pass --force to regenerate it.",
+                    config.display_path(path)
+                );
+            }
+            Step::CreateFile(path) => {
+                safe_create_file(path).map_err(|e| format!("Failed to create file: {e}"))?;
+                println!("Created empty file \"{}\"", config.display_path(path));
+            }
+            Step::ExtractExample(path) => {
+                match puzzle.as_deref().and_then(scaffold::extract_example) {
+                    Some(example) => {
+                        fs::write(path, example)
+                            .map_err(|e| format!("Failed to write example file: {e}"))?;
+                        println!("Extracted example to \"{}\"", config.display_path(path));
+                    }
+                    None if path.exists() => {}
+                    None => {
+                        safe_create_file(path)
+                            .map_err(|e| format!("Failed to create file: {e}"))?;
+                        println!("Created empty file \"{}\"", config.display_path(path));
+                    }
+                }
+            }
+            Step::KeepFile(_) => {}
         }
     }
 
-    println!("---");
-    println!(
-        "🎄 Type `cargo solve {}` to run your solution.",
-        &day_padded
-    );
+    Ok(())
 }
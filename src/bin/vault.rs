@@ -0,0 +1,129 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use advent_of_code::{
+    config::Config,
+    vault::{self, Vault, VaultError},
+};
+use std::{fs, path::PathBuf, process};
+
+enum Command {
+    Init,
+    Seal,
+    Unseal,
+}
+
+struct Args {
+    command: Command,
+    force: bool,
+}
+
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let mut args = pico_args::Arguments::from_env();
+    let force = args.contains(["-f", "--force"]);
+    let command = match args.free_from_str::<String>()?.as_str() {
+        "init" => Command::Init,
+        "seal" => Command::Seal,
+        "unseal" => Command::Unseal,
+        _ => {
+            return Err(pico_args::Error::ArgumentParsingFailed {
+                cause: "expected one of \"init\", \"seal\" or \"unseal\"".into(),
+            })
+        }
+    };
+    Ok(Args { command, force })
+}
+
+/// Files in the inputs folder with the given suffix, sorted by name.
+fn input_files(suffix: &str) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(&Config::get().paths.inputs)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.to_string_lossy().ends_with(suffix))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+/// Encrypts every input. Sealed files whose content did not change are left alone, to keep git history quiet.
+fn seal(vault: &Vault) -> Result<(), VaultError> {
+    let config = Config::get();
+    for path in input_files(".txt") {
+        let plaintext = fs::read_to_string(&path).map_err(|_| VaultError::IoError(path.clone()))?;
+        if plaintext.is_empty() {
+            continue;
+        }
+
+        let sealed_path = vault::sealed_path(&path);
+        if vault
+            .unseal_file(&sealed_path)
+            .is_ok_and(|sealed| sealed == plaintext)
+        {
+            println!("Unchanged \"{}\"", config.display_path(&sealed_path));
+            continue;
+        }
+
+        fs::write(&sealed_path, vault.seal(plaintext.as_bytes()))
+            .map_err(|_| VaultError::IoError(sealed_path.clone()))?;
+        println!("Sealed \"{}\"", config.display_path(&sealed_path));
+    }
+    Ok(())
+}
+
+/// Decrypts every sealed input. Existing inputs with different content are only overwritten with `force`.
+fn unseal(vault: &Vault, force: bool) -> Result<(), VaultError> {
+    let config = Config::get();
+    for sealed_path in input_files(&format!(".txt.{}", vault::SEALED_EXTENSION)) {
+        let plaintext = vault.unseal_file(&sealed_path)?;
+        let path = sealed_path.with_extension("");
+
+        match fs::read_to_string(&path) {
+            Ok(existing) if existing == plaintext => {
+                println!("Unchanged \"{}\"", config.display_path(&path));
+                continue;
+            }
+            Ok(existing) if !existing.is_empty() && !force => {
+                println!(
+                    "Skipped \"{}\", it differs from the sealed copy. Pass --force to overwrite it.",
+                    config.display_path(&path)
+                );
+                continue;
+            }
+            _ => {}
+        }
+
+        fs::write(&path, plaintext).map_err(|_| VaultError::IoError(path.clone()))?;
+        println!("Unsealed \"{}\"", config.display_path(&path));
+    }
+    Ok(())
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {e}. example: `cargo vault seal`");
+            process::exit(1);
+        }
+    };
+
+    let result = match args.command {
+        Command::Init => Vault::init().map(|path| {
+            println!(
+                "🎄 Created key file \"{}\". Share it with your team, but never commit it.",
+                Config::get().display_path(&path)
+            );
+        }),
+        Command::Seal => Vault::load().and_then(|vault| seal(&vault)),
+        Command::Unseal => Vault::load().and_then(|vault| unseal(&vault, args.force)),
+    };
+
+    if let Err(e) = result {
+        eprintln!("Vault error: {e}");
+        process::exit(1);
+    }
+}
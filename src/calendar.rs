@@ -0,0 +1,97 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::Day;
+
+pub const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Current unix timestamp in seconds.
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Converts days since the unix epoch to a `(year, month, day)` date.
+/// see: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Converts a `(year, month, day)` date to days since the unix epoch.
+/// see: http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = i64::from((month + 9) % 12);
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// The most recent advent of code event at `timestamp`: the current year in December, the previous year otherwise.
+pub fn latest_event_year(timestamp: u64) -> u16 {
+    let (year, month, _) = civil_from_days((timestamp / SECONDS_PER_DAY) as i64);
+    let year = if month == 12 { year } else { year - 1 };
+    year as u16
+}
+
+/// Puzzles unlock at midnight US Eastern time, which is UTC-5 throughout December.
+const UNLOCK_UTC_OFFSET: u64 = 5 * 3600;
+
+/// Unix timestamp at which the puzzle for `day` of `year` unlocks.
+pub fn unlock_time(year: u16, day: Day) -> u64 {
+    days_from_civil(i64::from(year), 12, u32::from(day.into_inner())) as u64 * SECONDS_PER_DAY
+        + UNLOCK_UTC_OFFSET
+}
+
+/// Calendar year (UTC) at `timestamp`.
+pub fn current_year(timestamp: u64) -> u16 {
+    civil_from_days((timestamp / SECONDS_PER_DAY) as i64).0 as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_civil_roundtrip() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(days_from_civil(2022, 12, 1), 19327);
+        assert_eq!(civil_from_days(19327), (2022, 12, 1));
+        assert_eq!(civil_from_days(days_from_civil(2024, 2, 29)), (2024, 2, 29));
+    }
+
+    #[test]
+    fn test_latest_event_year() {
+        // 2022-11-30T23:59:59Z
+        assert_eq!(latest_event_year(1_669_852_799), 2021);
+        // 2022-12-01T00:00:00Z
+        assert_eq!(latest_event_year(1_669_852_800), 2022);
+        // 2023-06-01T00:00:00Z
+        assert_eq!(latest_event_year(1_685_577_600), 2022);
+    }
+
+    #[test]
+    fn test_unlock_time() {
+        // 2022-12-01T05:00:00Z
+        assert_eq!(unlock_time(2022, crate::day!(1)), 1_669_870_800);
+        // 2022-12-25T05:00:00Z
+        assert_eq!(unlock_time(2022, crate::day!(25)), 1_671_944_400);
+        assert_eq!(current_year(1_669_870_800), 2022);
+    }
+}
@@ -0,0 +1,365 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::OnceLock,
+};
+use toml::{Table, Value};
+
+/// Name of the project configuration file, looked up in the current directory and its ancestors.
+pub const CONFIG_FILE: &str = ".aoc.toml";
+
+#[derive(Debug)]
+pub enum ConfigError {
+    IoError(PathBuf),
+    InvalidToml(String),
+    InvalidValue(String, String),
+    UnknownKey(String),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::IoError(path) => write!(f, "could not read \"{}\".", path.display()),
+            ConfigError::InvalidToml(e) => write!(f, "invalid toml: {}", e.trim()),
+            ConfigError::InvalidValue(key, expected) => {
+                write!(f, "\"{key}\" should be {expected}.")
+            }
+            ConfigError::UnknownKey(key) => write!(f, "unknown key \"{key}\"."),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(format!(
+                "unknown format \"{s}\", expected \"text\" or \"json\""
+            )),
+        }
+    }
+}
+
+/// Directories and files the template reads from and writes to, resolved against the project root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Paths {
+    pub bin: PathBuf,
+    pub inputs: PathBuf,
+    pub examples: PathBuf,
+    pub puzzles: PathBuf,
+    /// Module templates for `cargo scaffold`, one `<kind>.rs.tmpl` file per kind.
+    pub templates: PathBuf,
+}
+
+/// Defaults for `cargo all`. Each of these can be overridden with a command line flag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunnerOptions {
+    /// Build solutions in release mode. Follows the runner's own profile when unset.
+    pub release: Option<bool>,
+    /// Seconds after which a solution is killed.
+    pub timeout: Option<u64>,
+    /// Number of solutions to run in parallel.
+    pub jobs: usize,
+    pub format: OutputFormat,
+}
+
+impl Default for RunnerOptions {
+    fn default() -> Self {
+        Self {
+            release: None,
+            timeout: None,
+            jobs: 1,
+            format: OutputFormat::Text,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// Directory containing the config file, or the current directory if there is none.
+    pub root: PathBuf,
+    /// Event year used when `--year` is not passed. aoc-cli picks the latest event when unset.
+    pub year: Option<u16>,
+    /// Session cookie file. aoc-cli's default (`~/.adventofcode.session`) is used when unset.
+    pub session_file: Option<PathBuf>,
+    pub paths: Paths,
+    pub runner: RunnerOptions,
+    /// Key for the encrypted input vault. Never check this file in.
+    pub vault_key_file: PathBuf,
+}
+
+impl Config {
+    pub fn with_root(root: &Path) -> Self {
+        Self {
+            root: root.to_path_buf(),
+            year: None,
+            session_file: None,
+            paths: Paths {
+                bin: root.join("src").join("bin"),
+                inputs: root.join("src").join("inputs"),
+                examples: root.join("src").join("examples"),
+                puzzles: root.join("src").join("puzzles"),
+                templates: root.join("templates"),
+            },
+            runner: RunnerOptions::default(),
+            vault_key_file: root.join(".aoc_vault_key"),
+        }
+    }
+
+    pub fn parse(contents: &str, root: &Path) -> Result<Self, ConfigError> {
+        let table = contents
+            .parse::<Table>()
+            .map_err(|e| ConfigError::InvalidToml(e.to_string()))?;
+
+        let mut config = Self::with_root(root);
+
+        for (key, value) in &table {
+            match key.as_str() {
+                "year" => {
+                    config.year = Some(integer(key, value, 2015, "a year from 2015 to 65535")?)
+                }
+                "session_file" => config.session_file = Some(expand_home(&string(key, value)?)),
+                "paths" => {
+                    for (key, value) in section(key, value)? {
+                        let path = root.join(string(key, value)?);
+                        match key.as_str() {
+                            "bin" => config.paths.bin = path,
+                            "inputs" => config.paths.inputs = path,
+                            "examples" => config.paths.examples = path,
+                            "puzzles" => config.paths.puzzles = path,
+                            "templates" => config.paths.templates = path,
+                            _ => return Err(ConfigError::UnknownKey(format!("paths.{key}"))),
+                        }
+                    }
+                }
+                "runner" => {
+                    for (key, value) in section(key, value)? {
+                        let runner = &mut config.runner;
+                        match key.as_str() {
+                            "release" => runner.release = Some(boolean(key, value)?),
+                            "timeout" => {
+                                runner.timeout =
+                                    Some(integer(key, value, 1, "a number of seconds above 0")?)
+                            }
+                            "jobs" => {
+                                runner.jobs = integer(key, value, 1, "a number of jobs above 0")?
+                            }
+                            "format" => {
+                                runner.format = string(key, value)?.parse().map_err(|_| {
+                                    ConfigError::InvalidValue(
+                                        format!("runner.{key}"),
+                                        "\"text\" or \"json\"".into(),
+                                    )
+                                })?
+                            }
+                            _ => return Err(ConfigError::UnknownKey(format!("runner.{key}"))),
+                        }
+                    }
+                }
+                "vault" => {
+                    for (key, value) in section(key, value)? {
+                        match key.as_str() {
+                            "key_file" => {
+                                config.vault_key_file = root.join(expand_home(&string(key, value)?))
+                            }
+                            _ => return Err(ConfigError::UnknownKey(format!("vault.{key}"))),
+                        }
+                    }
+                }
+                _ => return Err(ConfigError::UnknownKey(key.into())),
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Loads the config file closest to the current directory, falling back to defaults if there is none.
+    pub fn load() -> Result<Self, ConfigError> {
+        let cwd = env::current_dir().map_err(|_| ConfigError::IoError(".".into()))?;
+
+        match cwd.ancestors().find(|dir| dir.join(CONFIG_FILE).is_file()) {
+            Some(root) => {
+                let path = root.join(CONFIG_FILE);
+                let contents = fs::read_to_string(&path).map_err(|_| ConfigError::IoError(path))?;
+                Self::parse(&contents, root)
+            }
+            None => Ok(Self::with_root(&cwd)),
+        }
+    }
+
+    /// The config for this process, loaded once. Exits if the config file is invalid.
+    pub fn get() -> &'static Config {
+        static CONFIG: OnceLock<Config> = OnceLock::new();
+        CONFIG.get_or_init(|| match Self::load() {
+            Ok(config) => config,
+            Err(e) => {
+                eprintln!("Failed to load \"{CONFIG_FILE}\": {e}");
+                std::process::exit(1);
+            }
+        })
+    }
+
+    /// Formats `path` relative to the project root if it is inside of it.
+    pub fn display_path(&self, path: &Path) -> String {
+        path.strip_prefix(&self.root)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    }
+
+    /// Resolves a data folder name as used by `read_file` (`inputs`, `examples`, `puzzles`).
+    pub fn folder(&self, name: &str) -> PathBuf {
+        match name {
+            "inputs" => self.paths.inputs.clone(),
+            "examples" => self.paths.examples.clone(),
+            "puzzles" => self.paths.puzzles.clone(),
+            _ => self.root.join("src").join(name),
+        }
+    }
+}
+
+fn section<'a>(key: &str, value: &'a Value) -> Result<&'a Table, ConfigError> {
+    value
+        .as_table()
+        .ok_or_else(|| ConfigError::InvalidValue(key.into(), "a table".into()))
+}
+
+fn string(key: &str, value: &Value) -> Result<String, ConfigError> {
+    value
+        .as_str()
+        .map(String::from)
+        .ok_or_else(|| ConfigError::InvalidValue(key.into(), "a string".into()))
+}
+
+fn boolean(key: &str, value: &Value) -> Result<bool, ConfigError> {
+    value
+        .as_bool()
+        .ok_or_else(|| ConfigError::InvalidValue(key.into(), "true or false".into()))
+}
+
+/// An integer of at least `min` that fits into `T`. `expected` describes the value in the error.
+fn integer<T: TryFrom<i64>>(
+    key: &str,
+    value: &Value,
+    min: i64,
+    expected: &str,
+) -> Result<T, ConfigError> {
+    value
+        .as_integer()
+        .filter(|n| *n >= min)
+        .and_then(|n| T::try_from(n).ok())
+        .ok_or_else(|| ConfigError::InvalidValue(key.into(), expected.into()))
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => env::var_os("HOME")
+            .or_else(|| env::var_os("USERPROFILE"))
+            .map_or_else(
+                || PathBuf::from(path),
+                |home| PathBuf::from(home).join(rest),
+            ),
+        None => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_defaults() {
+        let root = Path::new("/aoc");
+        let config = Config::parse("", root).unwrap();
+        assert_eq!(config, Config::with_root(root));
+        assert_eq!(config.folder("inputs"), Path::new("/aoc/src/inputs"));
+        assert_eq!(config.runner.jobs, 1);
+    }
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse(
+            r#"
+            year = 2021
+            session_file = "/secrets/aoc"
+
+            [paths]
+            inputs = "data/inputs"
+            templates = "scaffold"
+
+            [runner]
+            release = true
+            timeout = 10
+            jobs = 4
+            format = "json"
+
+            [vault]
+            key_file = "~/aoc.key"
+            "#,
+            Path::new("/aoc"),
+        )
+        .unwrap();
+
+        assert_eq!(config.year, Some(2021));
+        assert!(config.vault_key_file.ends_with("aoc.key"));
+        assert_eq!(config.session_file, Some(PathBuf::from("/secrets/aoc")));
+        assert_eq!(config.folder("inputs"), Path::new("/aoc/data/inputs"));
+        assert_eq!(config.folder("examples"), Path::new("/aoc/src/examples"));
+        assert_eq!(config.paths.templates, PathBuf::from("/aoc/scaffold"));
+        assert_eq!(
+            config.runner,
+            RunnerOptions {
+                release: Some(true),
+                timeout: Some(10),
+                jobs: 4,
+                format: OutputFormat::Json,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let root = Path::new("/aoc");
+        assert!(matches!(
+            Config::parse("yaer = 2021", root),
+            Err(ConfigError::UnknownKey(key)) if key == "yaer"
+        ));
+        assert!(matches!(
+            Config::parse("year = \"2021\"", root),
+            Err(ConfigError::InvalidValue(..))
+        ));
+        assert_eq!(
+            Config::parse("year = 1999", root).unwrap_err().to_string(),
+            "\"year\" should be a year from 2015 to 65535."
+        );
+        assert!(matches!(
+            Config::parse("[runner]\njobs = 0", root),
+            Err(ConfigError::InvalidValue(key, _)) if key == "jobs"
+        ));
+        assert!(matches!(
+            Config::parse("[runner]\nformat = \"xml\"", root),
+            Err(ConfigError::InvalidValue(..))
+        ));
+        assert!(matches!(
+            Config::parse("year = ", root),
+            Err(ConfigError::InvalidToml(_))
+        ));
+    }
+}
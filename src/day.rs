@@ -0,0 +1,133 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{
+    error::Error,
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// A day of the advent calendar. Only 1 to 25 are valid.
+///
+/// In solutions, use the [`day!`](crate::day!) macro, which checks the day at compile time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Day(u8);
+
+impl Day {
+    pub const fn new(day: u8) -> Option<Self> {
+        if matches!(day, 1..=25) {
+            Some(Self(day))
+        } else {
+            None
+        }
+    }
+
+    /// All days of the calendar, in order.
+    pub fn all() -> impl Iterator<Item = Day> {
+        (1..=25).map(Self)
+    }
+
+    pub const fn into_inner(self) -> u8 {
+        self.0
+    }
+
+    /// Position of the day in per-day arrays, starting at 0 for day 1.
+    pub fn index(self) -> usize {
+        usize::from(self.0) - 1
+    }
+
+    /// Path of the file for this day in `dir`, e.g. `src/inputs/01.txt`.
+    pub fn path(self, dir: &Path, extension: &str) -> PathBuf {
+        dir.join(format!("{self}.{extension}"))
+    }
+
+    /// Puzzle page for this day on adventofcode.com.
+    pub fn url(self, year: u16) -> String {
+        format!("https://adventofcode.com/{year}/day/{}", self.0)
+    }
+}
+
+/// Zero-padded, matching the file names: `01`, `02`, ... `25`.
+impl Display for Day {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:02}", self.0)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct DayError(String);
+
+impl Display for DayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "\"{}\" is not a valid day, expected a number between 1 and 25.",
+            self.0
+        )
+    }
+}
+
+impl Error for DayError {}
+
+impl FromStr for Day {
+    type Err = DayError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.trim()
+            .parse()
+            .ok()
+            .and_then(Self::new)
+            .ok_or_else(|| DayError(s.into()))
+    }
+}
+
+impl TryFrom<u8> for Day {
+    type Error = DayError;
+
+    fn try_from(day: u8) -> Result<Self, Self::Error> {
+        Self::new(day).ok_or_else(|| DayError(day.to_string()))
+    }
+}
+
+/// Creates a [`Day`], failing to compile if the day is not between 1 and 25.
+#[macro_export]
+macro_rules! day {
+    ($day:expr) => {{
+        const DAY: $crate::Day = match $crate::Day::new($day) {
+            Some(day) => day,
+            None => panic!("a day must be between 1 and 25"),
+        };
+        DAY
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("7".parse(), Ok(Day(7)));
+        assert_eq!("07".parse(), Ok(Day(7)));
+        assert_eq!("25".parse(), Ok(Day(25)));
+        assert_eq!("0".parse::<Day>(), Err(DayError("0".into())));
+        assert_eq!("99".parse::<Day>(), Err(DayError("99".into())));
+        assert_eq!("one".parse::<Day>(), Err(DayError("one".into())));
+        assert_eq!(Day::try_from(26), Err(DayError("26".into())));
+    }
+
+    #[test]
+    fn test_conversions() {
+        let day = crate::day!(7);
+        assert_eq!(day.to_string(), "07");
+        assert_eq!(day.index(), 6);
+        assert_eq!(
+            day.path(Path::new("src/inputs"), "txt"),
+            Path::new("src/inputs/07.txt")
+        );
+        assert_eq!(day.url(2022), "https://adventofcode.com/2022/day/7");
+        assert_eq!(Day::all().count(), 25);
+    }
+}
@@ -2,3 +2,27 @@
  * Use this file if you want to extract helpers from your solutions.
  * Example import from this file: `use advent_of_code::helpers::example_fn;`.
  */
+pub mod charset;
+pub mod cycle;
+pub mod direction;
+pub mod grid;
+pub mod interval;
+pub mod iter;
+pub mod math;
+pub mod memo;
+pub mod ocr;
+pub mod parse;
+pub mod point;
+pub mod search;
+pub mod sparse;
+pub mod viz;
+
+pub use charset::CharSet;
+pub use direction::Direction;
+pub use grid::Grid;
+pub use interval::{Interval, RangeSet};
+pub use iter::IterExt;
+pub use memo::Memo;
+pub use point::Point;
+pub use search::{astar, bfs, dijkstra};
+pub use sparse::SparseGrid;
@@ -0,0 +1,165 @@
+use std::ops::{BitAnd, BitOr};
+
+/// A set of ASCII characters stored as a bitmask, so it never allocates.
+///
+/// # Panics
+/// Methods that take a character panic if it is not ASCII.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CharSet(u128);
+
+fn bit(ch: char) -> u128 {
+    assert!(ch.is_ascii(), "{ch:?} is not an ASCII character");
+    1 << ch as u32
+}
+
+impl CharSet {
+    pub const EMPTY: CharSet = CharSet(0);
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `ch`, returning whether it was not in the set before.
+    pub fn insert(&mut self, ch: char) -> bool {
+        let added = !self.contains(ch);
+        self.0 |= bit(ch);
+        added
+    }
+
+    /// Removes `ch`, returning whether it was in the set.
+    pub fn remove(&mut self, ch: char) -> bool {
+        let removed = self.contains(ch);
+        self.0 &= !bit(ch);
+        removed
+    }
+
+    /// Adds `ch` if it is missing and removes it otherwise.
+    pub fn toggle(&mut self, ch: char) {
+        self.0 ^= bit(ch);
+    }
+
+    pub fn contains(&self, ch: char) -> bool {
+        self.0 & bit(ch) != 0
+    }
+
+    pub fn intersection(self, other: CharSet) -> CharSet {
+        CharSet(self.0 & other.0)
+    }
+
+    pub fn union(self, other: CharSet) -> CharSet {
+        CharSet(self.0 | other.0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// The smallest character in the set.
+    pub fn first(&self) -> Option<char> {
+        (!self.is_empty()).then(|| char::from(self.0.trailing_zeros() as u8))
+    }
+
+    /// Characters in the set, in ASCII order.
+    pub fn iter(&self) -> impl Iterator<Item = char> {
+        let bits = self.0;
+        (0..128u8)
+            .filter(move |i| bits & (1 << i) != 0)
+            .map(char::from)
+    }
+
+    /// Sum of the [`priority`] of every character in the set.
+    pub fn priority(&self) -> u32 {
+        self.iter().map(priority).sum()
+    }
+}
+
+/// `a` to `z` have priorities 1 to 26 and `A` to `Z` have 27 to 52.
+///
+/// # Panics
+/// If `ch` is not an ASCII letter.
+pub fn priority(ch: char) -> u32 {
+    match ch {
+        'a'..='z' => ch as u32 - 'a' as u32 + 1,
+        'A'..='Z' => ch as u32 - 'A' as u32 + 27,
+        _ => panic!("{ch:?} has no priority"),
+    }
+}
+
+impl From<&str> for CharSet {
+    fn from(s: &str) -> Self {
+        s.chars().collect()
+    }
+}
+
+impl FromIterator<char> for CharSet {
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Self {
+        let mut set = CharSet::new();
+        for ch in iter {
+            set.insert(ch);
+        }
+        set
+    }
+}
+
+impl BitAnd for CharSet {
+    type Output = CharSet;
+
+    fn bitand(self, rhs: CharSet) -> Self::Output {
+        self.intersection(rhs)
+    }
+}
+
+impl BitOr for CharSet {
+    type Output = CharSet;
+
+    fn bitor(self, rhs: CharSet) -> Self::Output {
+        self.union(rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set() {
+        let mut set = CharSet::from("vJrwpWtwJgWr");
+        assert_eq!(set.len(), 8);
+        assert!(set.contains('W'));
+        assert!(!set.insert('J'));
+        assert!(set.insert('z'));
+        assert!(set.remove('z'));
+        assert!(!set.contains('z'));
+
+        let other = CharSet::from("hcsFMMfFFhFp");
+        assert_eq!((set & other).iter().collect::<String>(), "p");
+        assert_eq!((set | other).len(), 14);
+        assert_eq!(CharSet::EMPTY.first(), None);
+        assert_eq!(CharSet::from("z-a!").first(), Some('!'));
+    }
+
+    #[test]
+    fn test_toggle() {
+        let mut set = CharSet::new();
+        set.toggle('a');
+        set.toggle('b');
+        set.toggle('a');
+        assert_eq!(set, CharSet::from("b"));
+    }
+
+    #[test]
+    fn test_priority() {
+        assert_eq!(priority('a'), 1);
+        assert_eq!(priority('z'), 26);
+        assert_eq!(priority('A'), 27);
+        assert_eq!(priority('Z'), 52);
+        assert_eq!(
+            CharSet::from("pLPvts").priority(),
+            16 + 38 + 42 + 22 + 20 + 19
+        );
+    }
+}
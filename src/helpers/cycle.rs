@@ -0,0 +1,164 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// A sequence of states that starts repeating: the state after `start + period` steps
+/// has the same key as the state after `start` steps, and so on forever.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<S> {
+    /// Number of steps before the first state of the cycle.
+    pub start: usize,
+    pub period: usize,
+    /// The states after `0..=start + period` steps. The last one repeats the state at `start`.
+    pub states: Vec<S>,
+}
+
+impl<S> Cycle<S> {
+    /// Position in `states` of a state with the same key as the state after `n` steps.
+    pub fn index(&self, n: u64) -> usize {
+        if n < self.states.len() as u64 {
+            return n as usize;
+        }
+        let offset = (n - self.start as u64) % self.period as u64;
+        self.start + offset as usize
+    }
+
+    /// A state with the same key as the state after `n` steps.
+    pub fn nth(&self, n: u64) -> &S {
+        &self.states[self.index(n)]
+    }
+
+    /// The value after `n` steps of something that accumulates with every step, such as a score or a height.
+    /// Each pass through the cycle is assumed to add the same amount as the first one.
+    pub fn extrapolate(&self, n: u64, mut value: impl FnMut(&S) -> i64) -> i64 {
+        let index = self.index(n);
+        let current = value(&self.states[index]);
+        if index as u64 == n {
+            return current;
+        }
+
+        let per_cycle =
+            value(&self.states[self.start + self.period]) - value(&self.states[self.start]);
+        let cycles = (n - index as u64) / self.period as u64;
+        current + cycles as i64 * per_cycle
+    }
+}
+
+/// Runs `step` from `initial` until a state repeats, comparing states by `key`.
+///
+/// The key should contain everything that decides the following states and leave out
+/// counters that only grow, like the tick or a score, so they can be extrapolated.
+/// Does not return if the states never repeat.
+pub fn find_cycle<S, K>(
+    initial: S,
+    mut step: impl FnMut(&mut S),
+    mut key: impl FnMut(&S) -> K,
+) -> Cycle<S>
+where
+    S: Clone,
+    K: Eq + Hash,
+{
+    let mut seen = HashMap::new();
+    let mut states = vec![];
+    let mut state = initial;
+
+    loop {
+        if let Some(start) = seen.insert(key(&state), states.len()) {
+            let period = states.len() - start;
+            states.push(state);
+            return Cycle {
+                start,
+                period,
+                states,
+            };
+        }
+        states.push(state.clone());
+        step(&mut state);
+    }
+}
+
+/// Brent's algorithm: finds `(start, period)` like [`find_cycle`], but only keeps two states in memory.
+/// Useful when the states are large or the cycle is long. Does not return if the states never repeat.
+pub fn brent<S, K>(
+    initial: S,
+    mut step: impl FnMut(&mut S),
+    mut key: impl FnMut(&S) -> K,
+) -> (usize, usize)
+where
+    S: Clone,
+    K: PartialEq,
+{
+    // find the period by moving the hare ahead in powers of two until it meets the tortoise.
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = initial.clone();
+    step(&mut hare);
+
+    while key(&tortoise) != key(&hare) {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        step(&mut hare);
+        period += 1;
+    }
+
+    // with the hare one period ahead, both meet at the start of the cycle.
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..period {
+        step(&mut hare);
+    }
+
+    let mut start = 0;
+    while key(&tortoise) != key(&hare) {
+        step(&mut tortoise);
+        step(&mut hare);
+        start += 1;
+    }
+
+    (start, period)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0, 1, 3, 7, 15, 11, 3, ... modulo 20: starts repeating at step 2 with period 4.
+    fn next(x: &mut u32) {
+        *x = (*x * 2 + 1) % 20;
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let cycle = find_cycle(0, next, |x| *x);
+        assert_eq!(cycle.start, 2);
+        assert_eq!(cycle.period, 4);
+        assert_eq!(cycle.states, [0, 1, 3, 7, 15, 11, 3]);
+        assert_eq!(*cycle.nth(1), 1);
+        assert_eq!(*cycle.nth(1_000_000_002), 3);
+        assert_eq!(*cycle.nth(1_000_000_003), 7);
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        // the tick is left out of the key and grows by one each step.
+        let cycle = find_cycle(
+            (0, 0),
+            |(x, tick)| {
+                next(x);
+                *tick += 1;
+            },
+            |(x, _)| *x,
+        );
+        for n in [0, 5, 6, 7, 100, 1_000_000_000_000] {
+            assert_eq!(cycle.extrapolate(n, |(_, tick)| *tick), n as i64);
+        }
+    }
+
+    #[test]
+    fn test_brent() {
+        assert_eq!(brent(0, next, |x| *x), (2, 4));
+        assert_eq!(brent(3, next, |x| *x), (0, 4));
+    }
+}
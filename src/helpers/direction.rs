@@ -0,0 +1,148 @@
+use std::{fmt::Display, str::FromStr};
+
+use super::Point;
+
+/// A direction on the screen: `Up` decreases `y`, `Right` increases `x`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    /// Orthogonal directions, clockwise starting at `Up`.
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// Orthogonal and diagonal directions, clockwise starting at `Up`.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    /// Step of length one in this direction. Diagonals move one step on both axes.
+    pub fn vector(self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::UpRight => Point::new(1, -1),
+            Direction::Right => Point::new(1, 0),
+            Direction::DownRight => Point::new(1, 1),
+            Direction::Down => Point::new(0, 1),
+            Direction::DownLeft => Point::new(-1, 1),
+            Direction::Left => Point::new(-1, 0),
+            Direction::UpLeft => Point::new(-1, -1),
+        }
+    }
+
+    /// Rotates clockwise in steps of 45°.
+    pub fn rotate(self, steps: i32) -> Self {
+        let index = Self::ALL.iter().position(|d| *d == self).unwrap() as i32;
+        Self::ALL[(index + steps).rem_euclid(8) as usize]
+    }
+
+    /// Rotates by 90° clockwise.
+    pub fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    /// Rotates by 90° counter-clockwise.
+    pub fn turn_left(self) -> Self {
+        self.rotate(-2)
+    }
+
+    pub fn opposite(self) -> Self {
+        self.rotate(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        let vector = self.vector();
+        vector.x != 0 && vector.y != 0
+    }
+}
+
+/// Offset as used by [`Grid`](super::Grid), e.g. `grid.ray(x, y, Direction::Up.into())`.
+impl From<Direction> for (isize, isize) {
+    fn from(direction: Direction) -> Self {
+        let vector = direction.vector();
+        (vector.x as isize, vector.y as isize)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct DirectionError(String);
+
+impl Display for DirectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "\"{}\" is not a direction.", self.0)
+    }
+}
+
+/// Parses `U`/`D`/`L`/`R`, compass points (`N`, `NE`, ... `NW`) and arrows (`^`, `>`, `v`, `<`).
+impl FromStr for Direction {
+    type Err = DirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "U" | "N" | "^" => Ok(Direction::Up),
+            "NE" => Ok(Direction::UpRight),
+            "R" | "E" | ">" => Ok(Direction::Right),
+            "SE" => Ok(Direction::DownRight),
+            "D" | "S" | "v" => Ok(Direction::Down),
+            "SW" => Ok(Direction::DownLeft),
+            "L" | "W" | "<" => Ok(Direction::Left),
+            "NW" => Ok(Direction::UpLeft),
+            _ => Err(DirectionError(s.into())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("U".parse(), Ok(Direction::Up));
+        assert_eq!("W".parse(), Ok(Direction::Left));
+        assert_eq!("SE".parse(), Ok(Direction::DownRight));
+        assert_eq!("v".parse(), Ok(Direction::Down));
+        assert_eq!("X".parse::<Direction>(), Err(DirectionError("X".into())));
+    }
+
+    #[test]
+    fn test_rotate() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::UpLeft.turn_right(), Direction::UpRight);
+        assert_eq!(Direction::Right.opposite(), Direction::Left);
+        assert_eq!(Direction::Up.rotate(-1), Direction::UpLeft);
+        assert!(Direction::DownLeft.is_diagonal());
+    }
+
+    #[test]
+    fn test_vector() {
+        for direction in Direction::ALL {
+            assert_eq!(
+                direction.vector() + direction.opposite().vector(),
+                Point::ORIGIN
+            );
+        }
+        assert_eq!(<(isize, isize)>::from(Direction::Up), (0, -1));
+    }
+}
@@ -0,0 +1,263 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+/// Offsets `(dx, dy)` to the four orthogonal neighbours: up, right, down, left.
+pub const DIRECTIONS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets `(dx, dy)` to all eight neighbours, clockwise starting at the top.
+pub const DIRECTIONS_8: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// A rectangular grid, stored row by row. Cells are addressed as `(x, y)` with `(0, 0)` in the top left corner.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// # Panics
+    /// If `cells` does not contain exactly `width * height` elements.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "grid cells do not match size");
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses a grid of characters, one row per line, mapping each character to a cell.
+    ///
+    /// # Panics
+    /// If the lines have different lengths.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> T) -> Self {
+        let mut width = None;
+        let mut cells = vec![];
+
+        for line in input.lines() {
+            let len = line.chars().count();
+            assert_eq!(*width.get_or_insert(len), len, "grid rows differ in length");
+            cells.extend(line.chars().map(&mut f));
+        }
+
+        let width = width.unwrap_or_default();
+        let height = cells.len().checked_div(width).unwrap_or_default();
+        Self::new(width, height, cells)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.index_of(x, y).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.index_of(x, y).map(|i| &mut self.cells[i])
+    }
+
+    /// Position of `(x + dx, y + dy)`, if it is inside the grid.
+    pub fn offset(
+        &self,
+        (x, y): (usize, usize),
+        (dx, dy): (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let x = x.checked_add_signed(dx).filter(|x| *x < self.width)?;
+        let y = y.checked_add_signed(dy).filter(|y| *y < self.height)?;
+        Some((x, y))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics for a chunk size of 0.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is outside of the grid");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// All cells with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| ((i % self.width, i / self.width), cell))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Cells next to `(x, y)` in the given directions, skipping those outside of the grid.
+    pub fn neighbours<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        directions: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = ((usize, usize), &'a T)> {
+        directions
+            .iter()
+            .filter_map(move |&direction| self.offset((x, y), direction))
+            .map(|(x, y)| ((x, y), &self[(x, y)]))
+    }
+
+    /// Orthogonal neighbours of `(x, y)`.
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.neighbours(x, y, &DIRECTIONS_4)
+    }
+
+    /// Orthogonal and diagonal neighbours of `(x, y)`.
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.neighbours(x, y, &DIRECTIONS_8)
+    }
+
+    /// Cells from `(x, y)` towards the edge of the grid in `direction`, not including `(x, y)` itself.
+    pub fn ray(
+        &self,
+        x: usize,
+        y: usize,
+        direction: (isize, isize),
+    ) -> impl Iterator<Item = ((usize, usize), &T)> {
+        std::iter::successors(self.offset((x, y), direction), move |&position| {
+            self.offset(position, direction)
+        })
+        .map(|(x, y)| ((x, y), &self[(x, y)]))
+    }
+
+    /// Applies `f` to every cell, keeping the shape of the grid.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    fn index_of(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width && y < self.height).then_some(y * self.width + x)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Self::new(width, height, vec![value; width * height])
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        self.get(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) is outside of the grid"))
+    }
+}
+
+/// Prints one row per line, without separators between cells.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<u32> {
+        Grid::parse("123\n456", |c| c.to_digit(10).unwrap())
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(2, 1), Some(&6));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 2), None);
+        assert_eq!(grid.to_string(), "123\n456");
+    }
+
+    #[test]
+    #[should_panic(expected = "grid rows differ in length")]
+    fn test_parse_ragged() {
+        Grid::parse("123\n45", |c| c);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = grid();
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.column(1).collect::<Vec<_>>(), [&2, &5]);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.sum::<u32>())
+                .collect::<Vec<_>>(),
+            [5, 7, 9]
+        );
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = grid();
+        let values = |it: &mut dyn Iterator<Item = ((usize, usize), &u32)>| {
+            it.map(|(_, v)| *v).collect::<Vec<_>>()
+        };
+        assert_eq!(values(&mut grid.neighbours4(0, 0)), [2, 4]);
+        assert_eq!(values(&mut grid.neighbours4(1, 1)), [2, 6, 4]);
+        assert_eq!(values(&mut grid.neighbours8(1, 1)), [2, 3, 6, 4, 1]);
+    }
+
+    #[test]
+    fn test_ray() {
+        let grid = grid();
+        let ray: Vec<_> = grid.ray(0, 0, (1, 0)).collect();
+        assert_eq!(ray, [((1, 0), &2), ((2, 0), &3)]);
+        assert_eq!(grid.ray(2, 1, (1, 1)).count(), 0);
+        assert_eq!(
+            grid.ray(2, 1, (-1, -1))
+                .map(|(_, v)| *v)
+                .collect::<Vec<_>>(),
+            [2]
+        );
+    }
+}
@@ -0,0 +1,263 @@
+use std::{fmt::Display, ops::RangeInclusive, str::FromStr};
+
+/// An inclusive range of integers, such as the sections `2-4`. Never empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+// an interval always contains at least one value, so there is no `is_empty`.
+#[allow(clippy::len_without_is_empty)]
+impl Interval {
+    /// # Panics
+    /// If `start` is greater than `end`.
+    pub fn new(start: i64, end: i64) -> Self {
+        assert!(start <= end, "interval {start}-{end} is empty");
+        Self { start, end }
+    }
+
+    pub fn len(&self) -> u64 {
+        self.start.abs_diff(self.end) + 1
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.start <= value && value <= self.end
+    }
+
+    /// Whether `other` lies completely inside this interval.
+    pub fn contains_interval(&self, other: &Interval) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        self.start <= other.end && other.start <= self.end
+    }
+
+    pub fn intersection(&self, other: &Interval) -> Option<Interval> {
+        self.overlaps(other)
+            .then(|| Interval::new(self.start.max(other.start), self.end.min(other.end)))
+    }
+
+    /// Both intervals as one, if they overlap or are adjacent like `1-2` and `3-4`.
+    pub fn union(&self, other: &Interval) -> Option<Interval> {
+        let touches =
+            self.start <= other.end.saturating_add(1) && other.start <= self.end.saturating_add(1);
+        touches.then(|| Interval::new(self.start.min(other.start), self.end.max(other.end)))
+    }
+}
+
+impl From<RangeInclusive<i64>> for Interval {
+    fn from(range: RangeInclusive<i64>) -> Self {
+        Interval::new(*range.start(), *range.end())
+    }
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct IntervalError(String);
+
+impl Display for IntervalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "\"{}\" is not an interval like \"2-4\".", self.0)
+    }
+}
+
+/// Parses `start-end`. The start may be negative, e.g. `-3-4`.
+impl FromStr for Interval {
+    type Err = IntervalError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || IntervalError(s.into());
+        // skip the first character, which may be a sign.
+        let split = s
+            .get(1..)
+            .and_then(|rest| rest.find('-'))
+            .ok_or_else(error)?
+            + 1;
+        let start = s[..split].trim().parse().map_err(|_| error())?;
+        let end = s[split + 1..].trim().parse().map_err(|_| error())?;
+        if start > end {
+            return Err(error());
+        }
+        Ok(Interval::new(start, end))
+    }
+}
+
+/// A set of integers stored as sorted intervals. Overlapping and adjacent intervals are merged.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RangeSet {
+    intervals: Vec<Interval>,
+}
+
+impl RangeSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, interval: Interval) {
+        let mut merged = interval;
+        let mut intervals = Vec::with_capacity(self.intervals.len() + 1);
+
+        for existing in self.intervals.drain(..) {
+            match merged.union(&existing) {
+                Some(union) => merged = union,
+                None => intervals.push(existing),
+            }
+        }
+
+        let position = intervals.partition_point(|existing| existing.start < merged.start);
+        intervals.insert(position, merged);
+        self.intervals = intervals;
+    }
+
+    /// Removes all values of `interval` from the set.
+    pub fn remove(&mut self, interval: Interval) {
+        self.intervals = self
+            .intervals
+            .iter()
+            .flat_map(|existing| {
+                let before = (existing.start < interval.start)
+                    .then(|| Interval::new(existing.start, existing.end.min(interval.start - 1)));
+                let after = (existing.end > interval.end)
+                    .then(|| Interval::new(existing.start.max(interval.end + 1), existing.end));
+                before.into_iter().chain(after)
+            })
+            .collect();
+    }
+
+    /// Values in this set that are not in `other`.
+    pub fn difference(&self, other: &RangeSet) -> RangeSet {
+        let mut result = self.clone();
+        for interval in &other.intervals {
+            result.remove(*interval);
+        }
+        result
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let i = self
+            .intervals
+            .partition_point(|interval| interval.end < value);
+        self.intervals
+            .get(i)
+            .is_some_and(|interval| interval.contains(value))
+    }
+
+    /// Total number of values covered by the set.
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The disjoint intervals of the set, in order.
+    pub fn intervals(&self) -> &[Interval] {
+        &self.intervals
+    }
+
+    /// Values between the first and the last interval that are not covered.
+    pub fn gaps(&self) -> impl Iterator<Item = Interval> + '_ {
+        self.intervals
+            .windows(2)
+            .map(|pair| Interval::new(pair[0].end + 1, pair[1].start - 1))
+    }
+
+    /// Values in `bounds` that are not covered, e.g. the possible position of a beacon.
+    pub fn uncovered(&self, bounds: Interval) -> RangeSet {
+        RangeSet::from_iter([bounds]).difference(self)
+    }
+}
+
+impl FromIterator<Interval> for RangeSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        let mut set = RangeSet::new();
+        for interval in iter {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interval() {
+        let a = Interval::new(2, 8);
+        let b: Interval = "3-7".parse().unwrap();
+
+        assert!(a.contains_interval(&b));
+        assert!(!b.contains_interval(&a));
+        assert!(a.overlaps(&Interval::new(8, 9)));
+        assert!(!a.overlaps(&Interval::new(9, 9)));
+        assert_eq!(
+            a.intersection(&Interval::new(6, 12)),
+            Some(Interval::new(6, 8))
+        );
+        assert_eq!(a.union(&Interval::new(9, 10)), Some(Interval::new(2, 10)));
+        assert_eq!(a.union(&Interval::new(10, 10)), None);
+        assert_eq!(a.len(), 7);
+        assert_eq!(Interval::from(-2..=2).len(), 5);
+    }
+
+    #[test]
+    fn test_interval_parse() {
+        assert_eq!("-3-4".parse(), Ok(Interval::new(-3, 4)));
+        assert_eq!("-5--1".parse(), Ok(Interval::new(-5, -1)));
+        assert_eq!("4-3".parse::<Interval>(), Err(IntervalError("4-3".into())));
+        assert!("4".parse::<Interval>().is_err());
+    }
+
+    #[test]
+    fn test_range_set_insert() {
+        let set = RangeSet::from_iter([
+            Interval::new(10, 12),
+            Interval::new(1, 3),
+            Interval::new(4, 5),
+            Interval::new(20, 25),
+            Interval::new(11, 14),
+        ]);
+
+        assert_eq!(
+            set.intervals(),
+            [
+                Interval::new(1, 5),
+                Interval::new(10, 14),
+                Interval::new(20, 25)
+            ]
+        );
+        assert_eq!(set.len(), 16);
+        assert!(set.contains(13));
+        assert!(!set.contains(15));
+        assert_eq!(
+            set.gaps().collect::<Vec<_>>(),
+            [Interval::new(6, 9), Interval::new(15, 19)]
+        );
+    }
+
+    #[test]
+    fn test_range_set_remove() {
+        let mut set = RangeSet::from_iter([Interval::new(0, 10), Interval::new(20, 30)]);
+        set.remove(Interval::new(5, 22));
+        assert_eq!(
+            set.intervals(),
+            [Interval::new(0, 4), Interval::new(23, 30)]
+        );
+
+        let covered = RangeSet::from_iter([Interval::new(-2, 10), Interval::new(12, 25)]);
+        assert_eq!(
+            covered.uncovered(Interval::new(0, 20)).intervals(),
+            [Interval::new(11, 11)]
+        );
+        assert!(covered.difference(&covered).is_empty());
+    }
+}
@@ -0,0 +1,181 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    iter::Sum,
+};
+
+/// Extra iterator adapters, available on every iterator after `use advent_of_code::helpers::IterExt`.
+pub trait IterExt: Iterator + Sized {
+    /// The `k` largest items, largest first. Only keeps `k` items in memory instead of sorting all of them.
+    fn top_k(self, k: usize) -> Vec<Self::Item>
+    where
+        Self::Item: Ord,
+    {
+        let mut heap = BinaryHeap::with_capacity(k + 1);
+        for item in self {
+            heap.push(Reverse(item));
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+        // sorting the reversed items puts the largest first.
+        heap.into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(item)| item)
+            .collect()
+    }
+
+    /// Sum of the `k` largest items.
+    fn sum_top_k<S>(self, k: usize) -> S
+    where
+        Self::Item: Ord,
+        S: Sum<Self::Item>,
+    {
+        self.top_k(k).into_iter().sum()
+    }
+
+    /// How often each item occurs.
+    fn counts(self) -> HashMap<Self::Item, usize>
+    where
+        Self::Item: Eq + Hash,
+    {
+        let mut counts = HashMap::new();
+        for item in self {
+            *counts.entry(item).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// The smallest and the largest item in one pass, `None` for an empty iterator.
+    fn min_max(self) -> Option<(Self::Item, Self::Item)>
+    where
+        Self::Item: Ord + Clone,
+    {
+        self.min_max_by_key(|item| item.clone())
+    }
+
+    /// The items with the smallest and the largest key. Like [`Iterator::min_by_key`] and [`Iterator::max_by_key`],
+    /// ties go to the first smallest and the last largest item.
+    fn min_max_by_key<K: Ord>(
+        mut self,
+        mut key: impl FnMut(&Self::Item) -> K,
+    ) -> Option<(Self::Item, Self::Item)>
+    where
+        Self::Item: Clone,
+    {
+        let first = self.next()?;
+        let mut min = (key(&first), first.clone());
+        let mut max = (key(&first), first);
+
+        for item in self {
+            let item_key = key(&item);
+            // an item below the minimum can not also be the maximum.
+            if item_key < min.0 {
+                min = (item_key, item);
+            } else if item_key >= max.0 {
+                max = (item_key, item);
+            }
+        }
+
+        Some((min.1, max.1))
+    }
+
+    /// Indices of all items that satisfy `predicate`.
+    fn position_all(self, mut predicate: impl FnMut(&Self::Item) -> bool) -> Vec<usize> {
+        self.enumerate()
+            .filter(|(_, item)| predicate(item))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Overlapping windows of `size` consecutive items, like [`slice::windows`] but for any iterator.
+    ///
+    /// # Panics
+    /// If `size` is 0.
+    fn sliding_windows(self, size: usize) -> SlidingWindows<Self>
+    where
+        Self::Item: Clone,
+    {
+        assert!(size > 0, "window size must be at least 1");
+        SlidingWindows {
+            iter: self,
+            window: VecDeque::with_capacity(size),
+            size,
+        }
+    }
+}
+
+impl<I: Iterator> IterExt for I {}
+
+/// Iterator returned by [`IterExt::sliding_windows`].
+pub struct SlidingWindows<I: Iterator> {
+    iter: I,
+    window: VecDeque<I::Item>,
+    size: usize,
+}
+
+impl<I> Iterator for SlidingWindows<I>
+where
+    I: Iterator,
+    I::Item: Clone,
+{
+    type Item = Vec<I::Item>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.window.len() == self.size {
+            self.window.pop_front();
+        }
+        while self.window.len() < self.size {
+            self.window.push_back(self.iter.next()?);
+        }
+        Some(self.window.iter().cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_top_k() {
+        let values = [3, 9, 1, 7, 9, 4];
+        assert_eq!(values.into_iter().top_k(3), [9, 9, 7]);
+        assert_eq!(values.into_iter().top_k(10).len(), 6);
+        assert!(values.into_iter().top_k(0).is_empty());
+        assert_eq!(values.into_iter().sum_top_k::<u32>(2), 18);
+    }
+
+    #[test]
+    fn test_counts() {
+        let counts = "hello".chars().counts();
+        assert_eq!(counts[&'l'], 2);
+        assert_eq!(counts[&'h'], 1);
+        assert_eq!(counts.len(), 4);
+    }
+
+    #[test]
+    fn test_min_max() {
+        assert_eq!([3, 9, 1, 7].into_iter().min_max(), Some((1, 9)));
+        assert_eq!([5].into_iter().min_max(), Some((5, 5)));
+        assert_eq!(std::iter::empty::<u8>().min_max(), None);
+        assert_eq!(
+            ["bb", "a", "cc", "d"]
+                .into_iter()
+                .min_max_by_key(|s| s.len()),
+            Some(("a", "cc"))
+        );
+    }
+
+    #[test]
+    fn test_position_all() {
+        assert_eq!("#..#.#".chars().position_all(|ch| *ch == '#'), [0, 3, 5]);
+    }
+
+    #[test]
+    fn test_sliding_windows() {
+        let windows: Vec<_> = (1..=4).sliding_windows(2).collect();
+        assert_eq!(windows, [vec![1, 2], vec![2, 3], vec![3, 4]]);
+        assert_eq!((1..=2).sliding_windows(3).count(), 0);
+    }
+}
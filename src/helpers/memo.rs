@@ -0,0 +1,241 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    hash::Hash,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// Cache for a recursive function over a hashable key. The function takes the cache as an argument and
+/// wraps its body in [`get_or_compute`](Memo::get_or_compute), passing the cache on to its recursive calls.
+///
+/// A bounded cache evicts the least recently used entry when it is full.
+/// The statistics of every cache are added up when it is dropped and printed by `solve!`.
+#[derive(Debug)]
+pub struct Memo<K, V> {
+    /// Every entry with the tick when it was last used.
+    cache: HashMap<K, (V, u64)>,
+    /// Keys by the tick when they were last used, only kept for bounded caches.
+    recent: BTreeMap<u64, K>,
+    capacity: Option<usize>,
+    tick: u64,
+    stats: Stats,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+}
+
+impl Stats {
+    /// Share of lookups that were answered from the cache.
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            total => self.hits as f64 / total as f64,
+        }
+    }
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} hits, {} misses ({:.1}% hit rate)",
+            self.hits,
+            self.misses,
+            self.hit_rate() * 100.0
+        )?;
+        if self.evictions > 0 {
+            write!(f, ", {} evictions", self.evictions)?;
+        }
+        Ok(())
+    }
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            cache: HashMap::new(),
+            recent: BTreeMap::new(),
+            capacity: None,
+            tick: 0,
+            stats: Stats::default(),
+        }
+    }
+}
+
+/// A clone shares the cached entries but starts with empty statistics, so that dropping both
+/// doesn't count the same lookups twice.
+impl<K: Clone, V: Clone> Clone for Memo<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            cache: self.cache.clone(),
+            recent: self.recent.clone(),
+            capacity: self.capacity,
+            tick: self.tick,
+            stats: Stats::default(),
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A cache that holds at most `capacity` entries.
+    ///
+    /// # Panics
+    /// If `capacity` is 0.
+    pub fn bounded(capacity: usize) -> Self {
+        assert!(capacity > 0, "a bounded cache needs room for one entry");
+        let mut memo = Self::default();
+        memo.capacity = Some(capacity);
+        memo
+    }
+
+    /// The cached value for `key`, or the result of `f` which is then cached.
+    /// `f` receives the cache for its recursive calls.
+    pub fn get_or_compute(&mut self, key: K, f: impl FnOnce(&mut Self) -> V) -> V {
+        self.tick += 1;
+
+        if let Some((value, last_used)) = self.cache.get_mut(&key) {
+            self.stats.hits += 1;
+            if self.capacity.is_some() {
+                let key = self.recent.remove(last_used).unwrap();
+                self.recent.insert(self.tick, key);
+            }
+            *last_used = self.tick;
+            return value.clone();
+        }
+
+        self.stats.misses += 1;
+        let value = f(self);
+        self.insert(key, value.clone());
+        value
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        // the recursion may have cached the key already, and it ticked since.
+        self.tick += 1;
+        if let Some((_, last_used)) = self.cache.insert(key.clone(), (value, self.tick)) {
+            self.recent.remove(&last_used);
+        }
+
+        if let Some(capacity) = self.capacity {
+            self.recent.insert(self.tick, key);
+            while self.cache.len() > capacity {
+                let (_, oldest) = self.recent.pop_first().unwrap();
+                self.cache.remove(&oldest);
+                self.stats.evictions += 1;
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+}
+
+static HITS: AtomicU64 = AtomicU64::new(0);
+static MISSES: AtomicU64 = AtomicU64::new(0);
+static EVICTIONS: AtomicU64 = AtomicU64::new(0);
+
+impl<K, V> Drop for Memo<K, V> {
+    fn drop(&mut self) {
+        HITS.fetch_add(self.stats.hits, Ordering::Relaxed);
+        MISSES.fetch_add(self.stats.misses, Ordering::Relaxed);
+        EVICTIONS.fetch_add(self.stats.evictions, Ordering::Relaxed);
+    }
+}
+
+/// Statistics of all caches dropped since the last call, `None` if no cache was used. Called by `solve!`.
+pub fn take_stats() -> Option<Stats> {
+    let stats = Stats {
+        hits: HITS.swap(0, Ordering::Relaxed),
+        misses: MISSES.swap(0, Ordering::Relaxed),
+        evictions: EVICTIONS.swap(0, Ordering::Relaxed),
+    };
+    (stats != Stats::default()).then_some(stats)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fib(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+        memo.get_or_compute(n, |memo| {
+            if n < 2 {
+                n
+            } else {
+                fib(n - 1, memo) + fib(n - 2, memo)
+            }
+        })
+    }
+
+    #[test]
+    fn test_memo() {
+        let mut memo = Memo::new();
+        assert_eq!(fib(90, &mut memo), 2880067194370816120);
+        assert_eq!(memo.len(), 91);
+        assert_eq!(
+            memo.stats(),
+            Stats {
+                hits: 88,
+                misses: 91,
+                evictions: 0
+            }
+        );
+
+        fib(90, &mut memo);
+        assert_eq!(memo.stats().hits, 89);
+    }
+
+    #[test]
+    fn test_bounded() {
+        let mut memo = Memo::bounded(3);
+        assert_eq!(fib(90, &mut memo), 2880067194370816120);
+        assert_eq!(memo.len(), 3);
+        assert_eq!(memo.stats().evictions, 88);
+
+        // `a` is used again, so `b` is the least recently used entry.
+        let mut memo = Memo::bounded(2);
+        memo.get_or_compute("a", |_| 1);
+        memo.get_or_compute("b", |_| 2);
+        memo.get_or_compute("a", |_| unreachable!());
+        memo.get_or_compute("c", |_| 3);
+        assert_eq!(memo.get_or_compute("a", |_| 0), 1);
+        assert_eq!(memo.get_or_compute("b", |_| 0), 0);
+    }
+
+    #[test]
+    fn test_clone() {
+        let mut memo = Memo::new();
+        fib(10, &mut memo);
+        let mut clone = memo.clone();
+        assert_eq!(clone.stats(), Stats::default());
+        assert_eq!(clone.get_or_compute(10, |_| 0), 55);
+        assert_eq!(clone.stats().hits, 1);
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = Stats {
+            hits: 3,
+            misses: 1,
+            evictions: 0,
+        };
+        assert_eq!(stats.to_string(), "3 hits, 1 misses (75.0% hit rate)");
+        assert_eq!(Stats::default().hit_rate(), 0.0);
+    }
+}
@@ -0,0 +1,178 @@
+/// Letters drawn 6 pixels high, as on the CRT in 2022 day 10. Each letter is 4 pixels wide, except `Y`.
+const GLYPHS_6: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', ".###\n..#.\n..#.\n..#.\n..#.\n.###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// Letters drawn 10 pixels high and 6 pixels wide, as in 2018 day 10.
+const GLYPHS_10: [(char, &str); 15] = [
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+fn is_lit(ch: char) -> bool {
+    matches!(ch, '#' | '█')
+}
+
+/// Whether the glyph, padded with dark pixels to `width`, matches the columns of `rows` starting at `x`.
+fn matches(glyph: &str, rows: &[Vec<bool>], x: usize, width: usize) -> bool {
+    glyph.lines().zip(rows).all(|(glyph_row, row)| {
+        let glyph_row: Vec<bool> = glyph_row.chars().map(is_lit).collect();
+        (0..width).all(|dx| {
+            let pixel = row.get(x + dx).copied().unwrap_or(false);
+            pixel == glyph_row.get(dx).copied().unwrap_or(false)
+        })
+    })
+}
+
+/// Reads the block letters in a screen of `#` (or `█`) and `.` (or spaces), like the answer of 2022 day 10.
+/// Screens must be 6 or 10 pixels high. Returns `None` if a letter is not recognised.
+pub fn decode(screen: &str) -> Option<String> {
+    let rows: Vec<Vec<bool>> = screen
+        .lines()
+        .map(|line| line.chars().map(is_lit).collect())
+        .collect();
+
+    // letters are separated by one empty column on small screens and by two on large ones.
+    let (glyphs, cell): (&[(char, &str)], usize) = match rows.len() {
+        6 => (&GLYPHS_6, 5),
+        10 => (&GLYPHS_10, 8),
+        _ => return None,
+    };
+
+    // the last letter may not be followed by an empty column.
+    let width = rows.iter().map(Vec::len).max()?;
+    let letters = (0..width.div_ceil(cell))
+        .map(|i| {
+            glyphs
+                .iter()
+                .find(|(_, glyph)| matches(glyph, &rows, i * cell, cell))
+                .map(|(letter, _)| *letter)
+        })
+        .collect::<Option<String>>()?;
+
+    (!letters.is_empty()).then_some(letters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Draws `letters` next to each other, each padded with dark pixels to `cell` columns.
+    fn draw(letters: &str, glyphs: &[(char, &str)], cell: usize) -> String {
+        let rows = glyphs[0].1.lines().count();
+        (0..rows)
+            .map(|y| {
+                letters
+                    .chars()
+                    .map(|letter| {
+                        let (_, glyph) = glyphs.iter().find(|(ch, _)| *ch == letter).unwrap();
+                        format!("{:.<cell$}", glyph.lines().nth(y).unwrap())
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_decode_small() {
+        let screen = draw("RGZEHURK", &GLYPHS_6, 5);
+        assert_eq!(screen.lines().next().unwrap().len(), 40);
+        assert_eq!(decode(&screen), Some("RGZEHURK".into()));
+
+        let all: String = GLYPHS_6.iter().map(|(letter, _)| *letter).collect();
+        assert_eq!(decode(&draw(&all, &GLYPHS_6, 5)), Some(all));
+    }
+
+    #[test]
+    fn test_decode_large() {
+        let all: String = GLYPHS_10.iter().map(|(letter, _)| *letter).collect();
+        let screen = draw(&all, &GLYPHS_10, 8);
+        assert_eq!(decode(&screen), Some(all));
+        assert_eq!(decode(&screen.replace('.', " ")), decode(&screen));
+    }
+
+    #[test]
+    fn test_decode_unknown() {
+        assert_eq!(decode("#"), None);
+        assert_eq!(
+            decode(&"##..##..##..##..##..##..##..##..##..##..\n".repeat(6)),
+            None
+        );
+    }
+}
@@ -0,0 +1,298 @@
+use std::{any::type_name, borrow::Cow, fmt::Display, str::FromStr};
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// The text on this line does not have the shape of the pattern.
+    Mismatch(usize, String),
+    /// The field on this line could not be converted to its type.
+    InvalidField(usize, String),
+    /// The pattern has a different number of `{}` fields than the requested tuple: expected, found.
+    FieldCount(usize, usize),
+}
+
+impl ParseError {
+    /// Moves the error down by `lines`, for text that starts in the middle of the input.
+    fn shift(self, lines: usize) -> Self {
+        match self {
+            ParseError::Mismatch(line, pattern) => ParseError::Mismatch(line + lines, pattern),
+            ParseError::InvalidField(line, field) => ParseError::InvalidField(line + lines, field),
+            e => e,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::Mismatch(line, pattern) => {
+                write!(f, "line {line} does not match the pattern {pattern:?}.")
+            }
+            ParseError::InvalidField(line, field) => {
+                write!(f, "could not parse \"{field}\" on line {line}.")
+            }
+            ParseError::FieldCount(expected, found) => {
+                write!(f, "pattern has {found} fields, expected {expected}.")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Tuples that can be built from the fields of a pattern, see [`fields`].
+pub trait Fields: Sized {
+    const COUNT: usize;
+
+    /// Converts the fields, returning the index of the first one that cannot be parsed.
+    fn from_fields(fields: &[&str]) -> Result<Self, usize>;
+}
+
+macro_rules! impl_fields {
+    ($count:expr; $($t:ident $i:tt),+) => {
+        impl<$($t: FromStr),+> Fields for ($($t,)+) {
+            const COUNT: usize = $count;
+
+            fn from_fields(fields: &[&str]) -> Result<Self, usize> {
+                Ok(($(fields[$i].parse::<$t>().map_err(|_| -> usize { $i })?,)+))
+            }
+        }
+    };
+}
+
+impl_fields!(1; A 0);
+impl_fields!(2; A 0, B 1);
+impl_fields!(3; A 0, B 1, C 2);
+impl_fields!(4; A 0, B 1, C 2, D 3);
+impl_fields!(5; A 0, B 1, C 2, D 3, E 4);
+impl_fields!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+impl_fields!(7; A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_fields!(8; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+fn line_at(text: &str, offset: usize) -> usize {
+    text.as_bytes()[..offset]
+        .iter()
+        .filter(|byte| **byte == b'\n')
+        .count()
+        + 1
+}
+
+/// Offset in `text` where matching `literal` fails, used to point errors in multi-line patterns to the right line.
+/// The field before `literal` is assumed to end on the same line as the literal starts.
+fn divergence(text: &str, literal: &str) -> usize {
+    let head = literal.split_inclusive('\n').next().unwrap_or_default();
+    match text.find(head) {
+        Some(start) => {
+            let matched = text[start..]
+                .bytes()
+                .zip(literal.bytes())
+                .take_while(|(a, b)| a == b)
+                .count();
+            start + matched
+        }
+        None => 0,
+    }
+}
+
+/// Parses `text` with a pattern such as `"move {} from {} to {}"` into a tuple.
+///
+/// Each `{}` matches everything up to the text that follows it in the pattern, so fields must be separated by some text.
+/// Patterns may span several lines, errors point to the line where the text stops matching.
+/// Windows line endings in `text` match the `\n` in the pattern.
+pub fn fields<T: Fields>(text: &str, pattern: &str) -> Result<T, ParseError> {
+    let text: Cow<str> = match text.contains("\r\n") {
+        true => text.replace("\r\n", "\n").into(),
+        false => text.into(),
+    };
+    let text = text.as_ref();
+
+    let mut literals = pattern.split("{}");
+    let found = pattern.matches("{}").count();
+    if found != T::COUNT {
+        return Err(ParseError::FieldCount(T::COUNT, found));
+    }
+
+    let mismatch = |offset| ParseError::Mismatch(line_at(text, offset), pattern.into());
+
+    let prefix = literals.next().unwrap_or_default();
+    if !text.starts_with(prefix) {
+        return Err(mismatch(0));
+    }
+
+    let mut offset = prefix.len();
+    let mut fields = vec![];
+
+    for literal in literals {
+        let rest = &text[offset..];
+        let len = if literal.is_empty() {
+            rest.len()
+        } else {
+            rest.find(literal)
+                .ok_or_else(|| mismatch(offset + divergence(rest, literal)))?
+        };
+        fields.push((offset, &rest[..len]));
+        offset += len + literal.len();
+    }
+
+    if offset != text.len() {
+        return Err(mismatch(offset));
+    }
+
+    let values: Vec<&str> = fields.iter().map(|(_, field)| *field).collect();
+    T::from_fields(&values).map_err(|i| {
+        let (offset, field) = fields[i];
+        ParseError::InvalidField(line_at(text, offset), field.into())
+    })
+}
+
+/// Parses every line of `input` with the same pattern, see [`fields`].
+pub fn lines<T: Fields>(input: &str, pattern: &str) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| fields(line, pattern).map_err(|e| e.shift(i)))
+        .collect()
+}
+
+/// Parses every line of `input` with its `FromStr` implementation.
+pub fn from_lines<T: FromStr>(input: &str) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            line.parse()
+                .map_err(|_| ParseError::InvalidField(i + 1, line.into()))
+        })
+        .collect()
+}
+
+/// All integers in `text`, in order. A `-` counts as a sign unless it follows a letter or digit, so `2-4` yields `2` and `4`.
+///
+/// # Panics
+/// If a number does not fit into `T`, e.g. a negative number for an unsigned type.
+pub fn ints<T: FromStr>(text: &str) -> impl Iterator<Item = T> + '_ {
+    let bytes = text.as_bytes();
+    let mut i = 0;
+
+    std::iter::from_fn(move || {
+        while i < bytes.len() {
+            let start = i;
+            let is_sign = bytes[i] == b'-'
+                && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+                && (i == 0 || !bytes[i - 1].is_ascii_alphanumeric());
+
+            i += 1;
+            if is_sign || bytes[start].is_ascii_digit() {
+                while i < bytes.len() && bytes[i].is_ascii_digit() {
+                    i += 1;
+                }
+                let number = &text[start..i];
+                return Some(number.parse().unwrap_or_else(|_| {
+                    panic!("could not parse \"{number}\" as {}", type_name::<T>())
+                }));
+            }
+        }
+        None
+    })
+}
+
+/// Splits `input` into blocks separated by blank lines. Lines inside a block are kept as they are.
+pub fn blocks(input: &str) -> Vec<&str> {
+    let mut blocks = vec![];
+    let mut start = None;
+    let mut end = 0;
+    let mut offset = 0;
+
+    for line in input.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            if let Some(start) = start.take() {
+                blocks.push(&input[start..end]);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + line.trim_end_matches(['\n', '\r']).len();
+        }
+        offset += line.len();
+    }
+
+    if let Some(start) = start {
+        blocks.push(&input[start..end]);
+    }
+
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fields() {
+        assert_eq!(
+            fields("move 3 from 1 to 2", "move {} from {} to {}"),
+            Ok((3, 1, 2))
+        );
+        assert_eq!(
+            fields("x=-3, name: ab", "x={}, name: {}"),
+            Ok((-3, "ab".to_string()))
+        );
+        assert_eq!(fields("a: 1\nb: 2", "a: {}\nb: {}"), Ok((1, 2)));
+        assert_eq!(fields("a: 1\r\nb: 2", "a: {}\nb: {}"), Ok((1, 2)));
+    }
+
+    #[test]
+    fn test_fields_errors() {
+        let pattern = "Monkey {}:\n  Test: {}";
+        assert_eq!(
+            fields::<(u8, u8)>("Monkey 0:\n  Tset: 3", pattern),
+            Err(ParseError::Mismatch(2, pattern.into()))
+        );
+        assert_eq!(
+            fields::<(u8, u8)>("Monkey 0:\n  Test: x", pattern),
+            Err(ParseError::InvalidField(2, "x".into()))
+        );
+        assert_eq!(
+            fields::<(u8, u8)>("Monkey 0:\n  Test: 3 extra", "Monkey {}:\n  Test: {} e"),
+            Err(ParseError::Mismatch(2, "Monkey {}:\n  Test: {} e".into()))
+        );
+        assert_eq!(
+            fields::<(u8,)>("1 2", "{} {}"),
+            Err(ParseError::FieldCount(1, 2))
+        );
+    }
+
+    #[test]
+    fn test_lines() {
+        assert_eq!(lines("1-2\n3-4", "{}-{}"), Ok(vec![(1, 2), (3, 4)]));
+        assert_eq!(
+            lines::<(u8, u8)>("1-2\n3-4\n5+6", "{}-{}"),
+            Err(ParseError::Mismatch(3, "{}-{}".into()))
+        );
+        assert_eq!(
+            from_lines::<u8>("1\n2\nthree"),
+            Err(ParseError::InvalidField(3, "three".into()))
+        );
+    }
+
+    #[test]
+    fn test_ints() {
+        assert_eq!(
+            ints::<i32>("x=-3, y=12: 2-4 a-1").collect::<Vec<_>>(),
+            [-3, 12, 2, 4, 1]
+        );
+        assert_eq!(
+            ints::<u64>("Starting items: 79, 98").collect::<Vec<_>>(),
+            [79, 98]
+        );
+        assert_eq!(ints::<u8>("none").count(), 0);
+    }
+
+    #[test]
+    fn test_blocks() {
+        assert_eq!(
+            blocks("    [D]\n 1\n\nmove 1\nmove 2\n\n\nlast\n"),
+            ["    [D]\n 1", "move 1\nmove 2", "last"]
+        );
+        assert_eq!(blocks("a\r\n\r\nb\r\n"), ["a", "b"]);
+        assert!(blocks("\n\n").is_empty());
+    }
+}
@@ -0,0 +1,153 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use super::Direction;
+
+/// A point or vector in 2D. `y` grows downwards, matching the rows of the input and [`Grid`](super::Grid).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: i32, y: i32) -> Self {
+        Self { x, y }
+    }
+
+    /// Distance when moving only orthogonally.
+    pub fn manhattan(self, other: Point) -> i32 {
+        (self.x - other.x).abs() + (self.y - other.y).abs()
+    }
+
+    /// Distance when diagonal moves are allowed, i.e. the number of king moves on a chess board.
+    pub fn chebyshev(self, other: Point) -> i32 {
+        (self.x - other.x).abs().max((self.y - other.y).abs())
+    }
+
+    /// Sign of each component, turning a vector into a single step in its general direction.
+    pub fn signum(self) -> Point {
+        Point::new(self.x.signum(), self.y.signum())
+    }
+
+    /// Orthogonal neighbours, clockwise starting at the top.
+    pub fn neighbours4(self) -> impl Iterator<Item = Point> {
+        Direction::CARDINAL.into_iter().map(move |d| self + d)
+    }
+
+    /// Orthogonal and diagonal neighbours, clockwise starting at the top.
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        Direction::ALL.into_iter().map(move |d| self + d)
+    }
+
+    /// Position in a [`Grid`](super::Grid), if neither coordinate is negative.
+    pub fn to_grid(self) -> Option<(usize, usize)> {
+        Some((usize::try_from(self.x).ok()?, usize::try_from(self.y).ok()?))
+    }
+}
+
+impl From<(i32, i32)> for Point {
+    fn from((x, y): (i32, i32)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Self::Output {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Add<Direction> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Direction) -> Self::Output {
+        self + rhs.vector()
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Self::Output {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Point) {
+        *self = *self + rhs;
+    }
+}
+
+impl AddAssign<Direction> for Point {
+    fn add_assign(&mut self, rhs: Direction) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Point) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: i32) -> Self::Output {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Self::Output {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ops() {
+        let mut p = Point::new(1, 2);
+        p += Point::new(2, -3);
+        assert_eq!(p, Point::new(3, -1));
+        assert_eq!(p - Point::new(3, 3), Point::new(0, -4));
+        assert_eq!(p * 2, Point::new(6, -2));
+        assert_eq!(-p, Point::new(-3, 1));
+        assert_eq!(p + Direction::Up, Point::new(3, -2));
+    }
+
+    #[test]
+    fn test_distances() {
+        let (a, b) = (Point::new(1, 1), Point::new(4, -1));
+        assert_eq!(a.manhattan(b), 5);
+        assert_eq!(a.chebyshev(b), 3);
+        assert_eq!((b - a).signum(), Point::new(1, -1));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let neighbours: Vec<_> = Point::ORIGIN.neighbours4().collect();
+        assert_eq!(
+            neighbours,
+            [
+                Point::new(0, -1),
+                Point::new(1, 0),
+                Point::new(0, 1),
+                Point::new(-1, 0)
+            ]
+        );
+        assert_eq!(Point::ORIGIN.neighbours8().count(), 8);
+        assert_eq!(Point::new(2, 3).to_grid(), Some((2, 3)));
+        assert_eq!(Point::new(-1, 3).to_grid(), None);
+    }
+}
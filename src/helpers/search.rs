@@ -0,0 +1,303 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+use super::Grid;
+
+/// A path found by a search, from one of the start states to the goal, both included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult<N, C> {
+    pub cost: C,
+    pub path: Vec<N>,
+}
+
+impl<N, C> SearchResult<N, C> {
+    pub fn goal(&self) -> &N {
+        self.path.last().unwrap()
+    }
+}
+
+fn reconstruct<N: Clone>(nodes: &[(N, Option<usize>)], goal: usize) -> Vec<N> {
+    let mut path: Vec<N> = std::iter::successors(Some(goal), |&i| nodes[i].1)
+        .map(|i| nodes[i].0.clone())
+        .collect();
+    path.reverse();
+    path
+}
+
+/// Breadth-first search where every step costs one. Returns the shortest path to the first state that satisfies `goal`.
+///
+/// Pass several start states to search from all of them at once.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<SearchResult<N, usize>>
+where
+    N: Eq + Hash + Clone,
+    I: IntoIterator<Item = N>,
+{
+    // every state with the index of the state it was reached from.
+    let mut nodes: Vec<(N, Option<usize>)> = vec![];
+    let mut seen = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if !seen.contains_key(&start) {
+            seen.insert(start.clone(), nodes.len());
+            queue.push_back(nodes.len());
+            nodes.push((start, None));
+        }
+    }
+
+    while let Some(i) = queue.pop_front() {
+        if goal(&nodes[i].0) {
+            let path = reconstruct(&nodes, i);
+            return Some(SearchResult {
+                cost: path.len() - 1,
+                path,
+            });
+        }
+
+        for next in neighbours(&nodes[i].0) {
+            if !seen.contains_key(&next) {
+                seen.insert(next.clone(), nodes.len());
+                queue.push_back(nodes.len());
+                nodes.push((next, Some(i)));
+            }
+        }
+    }
+
+    None
+}
+
+/// Cheapest path with weighted steps. `neighbours` returns the next states together with the cost of the step.
+/// Costs must not be negative.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+    goal: impl FnMut(&N) -> bool,
+) -> Option<SearchResult<N, C>>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, neighbours, |_| C::default(), goal)
+}
+
+/// Like [`dijkstra`], but explores states in order of their cost plus `heuristic`, an estimate of the remaining cost.
+/// The result is the cheapest path as long as the heuristic never overestimates, e.g. the Manhattan distance on a grid.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<SearchResult<N, C>>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut nodes: Vec<(N, Option<usize>)> = vec![];
+    let mut costs: Vec<C> = vec![];
+    let mut seen = HashMap::new();
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        if !seen.contains_key(&start) {
+            let i = nodes.len();
+            heap.push(Reverse((heuristic(&start), C::default(), i)));
+            seen.insert(start.clone(), i);
+            nodes.push((start, None));
+            costs.push(C::default());
+        }
+    }
+
+    while let Some(Reverse((_, cost, i))) = heap.pop() {
+        // a cheaper way to this state was found after this entry was queued.
+        if cost > costs[i] {
+            continue;
+        }
+
+        if goal(&nodes[i].0) {
+            return Some(SearchResult {
+                cost,
+                path: reconstruct(&nodes, i),
+            });
+        }
+
+        for (next, step) in neighbours(&nodes[i].0) {
+            let next_cost = cost + step;
+            let j = match seen.get(&next) {
+                Some(&j) if costs[j] <= next_cost => continue,
+                Some(&j) => {
+                    costs[j] = next_cost;
+                    nodes[j].1 = Some(i);
+                    j
+                }
+                None => {
+                    let j = nodes.len();
+                    seen.insert(next.clone(), j);
+                    nodes.push((next.clone(), Some(i)));
+                    costs.push(next_cost);
+                    j
+                }
+            };
+            heap.push(Reverse((next_cost + heuristic(&next), next_cost, j)));
+        }
+    }
+
+    None
+}
+
+impl<T> Grid<T> {
+    /// Shortest path between cells, moving orthogonally. `can_move(from, to)` decides whether a step between two cells is allowed.
+    pub fn shortest_path(
+        &self,
+        starts: impl IntoIterator<Item = (usize, usize)>,
+        mut can_move: impl FnMut(&T, &T) -> bool,
+        mut goal: impl FnMut((usize, usize), &T) -> bool,
+    ) -> Option<SearchResult<(usize, usize), usize>> {
+        bfs(
+            starts,
+            |&(x, y)| {
+                let from = &self[(x, y)];
+                self.neighbours4(x, y)
+                    .filter(|(_, to)| can_move(from, to))
+                    .map(|(position, _)| position)
+                    .collect::<Vec<_>>()
+            },
+            |&position| goal(position, &self[position]),
+        )
+    }
+
+    /// Cheapest path between cells, moving orthogonally. `cost(from, to)` is the cost of a step, or `None` if it is not allowed.
+    pub fn cheapest_path<C>(
+        &self,
+        starts: impl IntoIterator<Item = (usize, usize)>,
+        mut cost: impl FnMut(&T, &T) -> Option<C>,
+        mut goal: impl FnMut((usize, usize), &T) -> bool,
+    ) -> Option<SearchResult<(usize, usize), C>>
+    where
+        C: Copy + Ord + Default + Add<Output = C>,
+    {
+        dijkstra(
+            starts,
+            |&(x, y)| {
+                let from = &self[(x, y)];
+                self.neighbours4(x, y)
+                    .filter_map(|(position, to)| Some((position, cost(from, to)?)))
+                    .collect::<Vec<_>>()
+            },
+            |&position| goal(position, &self[position]),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::Point;
+
+    const HILL: &str = "Sabqponm\nabcryxxl\naccszExk\nacctuvwj\nabdefghi";
+
+    fn elevation(ch: char) -> u8 {
+        match ch {
+            'S' => b'a',
+            'E' => b'z',
+            ch => ch as u8,
+        }
+    }
+
+    #[test]
+    fn test_grid_shortest_path() {
+        let grid = Grid::parse(HILL, |ch| ch);
+        let climb = |from: &char, to: &char| elevation(*to) <= elevation(*from) + 1;
+
+        let result = grid
+            .shortest_path([(0, 0)], climb, |_, ch| *ch == 'E')
+            .unwrap();
+        assert_eq!(result.cost, 31);
+        assert_eq!(result.path.len(), 32);
+        assert_eq!(result.path[0], (0, 0));
+        assert_eq!(*result.goal(), (5, 2));
+
+        // start from every lowest cell at once.
+        let starts: Vec<_> = grid
+            .iter()
+            .filter(|(_, ch)| elevation(**ch) == b'a')
+            .map(|(position, _)| position)
+            .collect();
+        let result = grid
+            .shortest_path(starts, climb, |_, ch| *ch == 'E')
+            .unwrap();
+        assert_eq!(result.cost, 29);
+    }
+
+    #[test]
+    fn test_grid_cheapest_path() {
+        let grid = Grid::parse("131\n191\n111", |ch| ch.to_digit(10).unwrap());
+        let result = grid
+            .cheapest_path(
+                [(0, 0)],
+                |_, to| Some(*to),
+                |position, _| position == (2, 2),
+            )
+            .unwrap();
+        assert_eq!(result.cost, 4);
+        assert_eq!(result.path, [(0, 0), (0, 1), (0, 2), (1, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // the direct edge is more expensive than the detour.
+        let edges = |n: &u8| match n {
+            0 => vec![(1, 10), (2, 1)],
+            2 => vec![(3, 1)],
+            3 => vec![(1, 1)],
+            _ => vec![],
+        };
+        let result = dijkstra([0], edges, |n| *n == 1).unwrap();
+        assert_eq!(
+            result,
+            SearchResult {
+                cost: 3,
+                path: vec![0, 2, 3, 1]
+            }
+        );
+        assert_eq!(dijkstra([1], edges, |n| *n == 0), None);
+    }
+
+    #[test]
+    fn test_astar() {
+        let goal = Point::new(3, 0);
+        let wall = |p: &Point| p.x == 1 && p.y < 2;
+        let open = |p: &Point| (-5..=5).contains(&p.x) && (-5..=5).contains(&p.y) && !wall(p);
+
+        let result = astar(
+            [Point::ORIGIN],
+            |p: &Point| {
+                p.neighbours4()
+                    .filter(open)
+                    .map(|n| (n, 1))
+                    .collect::<Vec<_>>()
+            },
+            |p| p.manhattan(goal),
+            |p| *p == goal,
+        )
+        .unwrap();
+        assert_eq!(result.cost, 7);
+
+        let bfs_result = bfs(
+            [Point::ORIGIN],
+            |p: &Point| p.neighbours4().filter(open).collect::<Vec<_>>(),
+            |p| *p == goal,
+        )
+        .unwrap();
+        assert_eq!(bfs_result.cost as i32, result.cost);
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+
+use super::Point;
+
+/// An unbounded grid that only stores occupied cells, for puzzles where coordinates can grow in any direction.
+/// Keeps track of the bounding box of all occupied cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    bounds: Option<(Point, Point)>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the value of a cell, returning the previous one.
+    pub fn insert(&mut self, position: Point, value: T) -> Option<T> {
+        self.grow(position);
+        self.cells.insert(position, value)
+    }
+
+    /// Clears a cell, shrinking the bounding box if the cell was on its edge.
+    pub fn remove(&mut self, position: Point) -> Option<T> {
+        let value = self.cells.remove(&position)?;
+        let (min, max) = self.bounds.unwrap();
+        if position.x == min.x || position.y == min.y || position.x == max.x || position.y == max.y
+        {
+            self.bounds = None;
+            let positions: Vec<Point> = self.cells.keys().copied().collect();
+            for position in positions {
+                self.grow(position);
+            }
+        }
+        Some(value)
+    }
+
+    fn grow(&mut self, position: Point) {
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (
+                Point::new(min.x.min(position.x), min.y.min(position.y)),
+                Point::new(max.x.max(position.x), max.y.max(position.y)),
+            ),
+            None => (position, position),
+        });
+    }
+
+    pub fn get(&self, position: Point) -> Option<&T> {
+        self.cells.get(&position)
+    }
+
+    pub fn get_mut(&mut self, position: Point) -> Option<&mut T> {
+        self.cells.get_mut(&position)
+    }
+
+    pub fn contains(&self, position: Point) -> bool {
+        self.cells.contains_key(&position)
+    }
+
+    /// The cell at `position`, inserting the value from `f` if it is empty.
+    pub fn get_or_insert_with(&mut self, position: Point, f: impl FnOnce() -> T) -> &mut T {
+        if !self.contains(position) {
+            self.insert(position, f());
+        }
+        self.cells.get_mut(&position).unwrap()
+    }
+
+    /// Number of occupied cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The top left and bottom right corner of the occupied cells, both included.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.bounds
+    }
+
+    /// Occupied cells in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells
+            .iter()
+            .map(|(position, value)| (*position, value))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Point> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// Draws the bounding box row by row, with `f` choosing the character for each occupied cell.
+    pub fn render(&self, empty: char, f: impl FnMut(Point, &T) -> char) -> String {
+        match self.bounds {
+            Some((min, max)) => self.render_viewport(min, max, empty, f),
+            None => String::new(),
+        }
+    }
+
+    /// Like [`render`](Self::render), but draws the area from `min` to `max`, both included, instead of the bounding box.
+    pub fn render_viewport(
+        &self,
+        min: Point,
+        max: Point,
+        empty: char,
+        mut f: impl FnMut(Point, &T) -> char,
+    ) -> String {
+        let mut output = String::new();
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let position = Point::new(x, y);
+                output.push(self.get(position).map_or(empty, |value| f(position, value)));
+            }
+            if y != max.y {
+                output.push('\n');
+            }
+        }
+        output
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        for (position, value) in iter {
+            grid.insert(position, value);
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+
+        grid.insert(Point::new(2, -1), 'a');
+        grid.insert(Point::new(-3, 4), 'b');
+        grid.insert(Point::new(0, 0), 'c');
+        assert_eq!(grid.bounds(), Some((Point::new(-3, -1), Point::new(2, 4))));
+
+        assert_eq!(grid.remove(Point::new(-3, 4)), Some('b'));
+        assert_eq!(grid.bounds(), Some((Point::new(0, -1), Point::new(2, 0))));
+        assert_eq!(grid.remove(Point::new(5, 5)), None);
+        assert_eq!(grid.len(), 2);
+    }
+
+    #[test]
+    fn test_render() {
+        let grid: SparseGrid<u8> = [(Point::new(-1, 0), 1), (Point::new(1, 1), 2)]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            grid.render('.', |_, value| (b'0' + value) as char),
+            "1..\n..2"
+        );
+        assert_eq!(
+            grid.render_viewport(Point::new(0, 0), Point::new(1, 2), ' ', |_, _| '#'),
+            "  \n #\n  "
+        );
+        assert_eq!(SparseGrid::<u8>::new().render('.', |_, _| '#'), "");
+    }
+}
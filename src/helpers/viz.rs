@@ -0,0 +1,531 @@
+use std::{
+    fmt::Display,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, OnceLock,
+    },
+    thread,
+    time::Duration,
+};
+
+use super::Grid;
+
+/// Size of a cell in pixels when a frame is written as an image.
+const SCALE: usize = 4;
+
+const DEFAULT_DELAY: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    pub const BACKGROUND: Rgb = Rgb::new(15, 15, 35);
+    pub const WHITE: Rgb = Rgb::new(204, 204, 204);
+    pub const GOLD: Rgb = Rgb::new(255, 255, 102);
+    pub const GREEN: Rgb = Rgb::new(0, 153, 0);
+    pub const RED: Rgb = Rgb::new(255, 68, 68);
+
+    const PALETTE: [Rgb; 6] = [
+        Rgb::GOLD,
+        Rgb::GREEN,
+        Rgb::RED,
+        Rgb::new(102, 153, 255),
+        Rgb::new(255, 153, 51),
+        Rgb::new(204, 102, 255),
+    ];
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Colour used when a text frame is written as an image.
+    /// Spaces and `.` are background, `#` is white and every other character gets one of a few colours.
+    pub fn for_char(ch: char) -> Rgb {
+        match ch {
+            ' ' | '.' => Rgb::BACKGROUND,
+            '#' => Rgb::WHITE,
+            ch => Rgb::PALETTE[ch as usize % Rgb::PALETTE.len()],
+        }
+    }
+}
+
+/// A single picture of a visualisation, either characters or colours.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Frame {
+    Text(Grid<char>),
+    Pixels(Grid<Rgb>),
+}
+
+impl Frame {
+    /// A text frame from lines of possibly different lengths, padded with spaces.
+    pub fn text(text: &str) -> Self {
+        let width = text.lines().map(|line| line.chars().count()).max();
+        let width = width.unwrap_or_default();
+        let cells: Vec<char> = text
+            .lines()
+            .flat_map(|line| line.chars().chain(std::iter::repeat(' ')).take(width))
+            .collect();
+        let height = cells.len().checked_div(width).unwrap_or_default();
+        Frame::Text(Grid::new(width, height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        match self {
+            Frame::Text(grid) => grid.width(),
+            Frame::Pixels(grid) => grid.width(),
+        }
+    }
+
+    pub fn height(&self) -> usize {
+        match self {
+            Frame::Text(grid) => grid.height(),
+            Frame::Pixels(grid) => grid.height(),
+        }
+    }
+
+    /// Colour of a cell, the background outside of the frame.
+    pub fn color(&self, x: usize, y: usize) -> Rgb {
+        match self {
+            Frame::Text(grid) => grid
+                .get(x, y)
+                .map_or(Rgb::BACKGROUND, |ch| Rgb::for_char(*ch)),
+            Frame::Pixels(grid) => grid.get(x, y).copied().unwrap_or(Rgb::BACKGROUND),
+        }
+    }
+
+    /// RGB bytes of an image of `width` by `height` cells, each drawn as a square of `scale` pixels.
+    fn to_rgb(&self, width: usize, height: usize, scale: usize) -> Vec<u8> {
+        let mut data = Vec::with_capacity(width * height * scale * scale * 3);
+        for y in 0..height * scale {
+            for x in 0..width * scale {
+                let color = self.color(x / scale, y / scale);
+                data.extend([color.r, color.g, color.b]);
+            }
+        }
+        data
+    }
+
+    /// The frame as text for a terminal. Colours are drawn with ANSI escape codes.
+    pub fn to_ansi(&self) -> String {
+        match self {
+            Frame::Text(grid) => grid.to_string(),
+            Frame::Pixels(grid) => grid
+                .rows()
+                .map(|row| {
+                    let mut line: String = row
+                        .iter()
+                        .map(|Rgb { r, g, b }| format!("\x1b[48;2;{r};{g};{b}m  "))
+                        .collect();
+                    line.push_str(crate::ANSI_RESET);
+                    line
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }
+    }
+}
+
+impl From<Grid<char>> for Frame {
+    fn from(grid: Grid<char>) -> Self {
+        Frame::Text(grid)
+    }
+}
+
+impl From<Grid<Rgb>> for Frame {
+    fn from(grid: Grid<Rgb>) -> Self {
+        Frame::Pixels(grid)
+    }
+}
+
+/// Receives the frames of a visualisation.
+pub trait Sink {
+    fn frame(&mut self, frame: &Frame) -> io::Result<()>;
+
+    /// Called after the last frame.
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Animates the frames in the terminal, waiting `delay` after each one.
+pub struct Terminal {
+    pub delay: Duration,
+}
+
+impl Sink for Terminal {
+    fn frame(&mut self, frame: &Frame) -> io::Result<()> {
+        let mut stdout = io::stdout().lock();
+        // move to the top left and clear the screen.
+        writeln!(stdout, "\x1b[H\x1b[2J{}", frame.to_ansi())?;
+        stdout.flush()?;
+        thread::sleep(self.delay);
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Ppm,
+    Png,
+}
+
+/// Writes every frame to a numbered image file in `dir`, e.g. `part1-0000.png`.
+pub struct FrameDir {
+    dir: PathBuf,
+    prefix: String,
+    format: ImageFormat,
+    count: usize,
+}
+
+impl FrameDir {
+    pub fn new(dir: &Path, prefix: &str, format: ImageFormat) -> io::Result<Self> {
+        fs::create_dir_all(dir)?;
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            prefix: prefix.into(),
+            format,
+            count: 0,
+        })
+    }
+}
+
+impl Sink for FrameDir {
+    fn frame(&mut self, frame: &Frame) -> io::Result<()> {
+        let (width, height) = (frame.width() * SCALE, frame.height() * SCALE);
+        let data = frame.to_rgb(frame.width(), frame.height(), SCALE);
+        let extension = match self.format {
+            ImageFormat::Ppm => "ppm",
+            ImageFormat::Png => "png",
+        };
+        let path = self
+            .dir
+            .join(format!("{}-{:04}.{extension}", self.prefix, self.count));
+        let mut file = BufWriter::new(File::create(path)?);
+
+        match self.format {
+            ImageFormat::Ppm => {
+                write!(file, "P6\n{width} {height}\n255\n")?;
+                file.write_all(&data)?;
+            }
+            ImageFormat::Png => {
+                let mut encoder = png::Encoder::new(file, width as u32, height as u32);
+                encoder.set_color(png::ColorType::Rgb);
+                encoder.set_depth(png::BitDepth::Eight);
+                encoder
+                    .write_header()
+                    .and_then(|mut writer| writer.write_image_data(&data))
+                    .map_err(io::Error::other)?;
+            }
+        }
+
+        self.count += 1;
+        Ok(())
+    }
+}
+
+/// Writes all frames into one animated GIF when the visualisation finishes.
+/// Frames are kept in memory until then, so the image can be as large as the largest frame.
+pub struct Gif {
+    path: PathBuf,
+    delay: Duration,
+    frames: Vec<Frame>,
+}
+
+impl Gif {
+    pub fn new(path: &Path, delay: Duration) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            delay,
+            frames: vec![],
+        }
+    }
+}
+
+impl Sink for Gif {
+    fn frame(&mut self, frame: &Frame) -> io::Result<()> {
+        self.frames.push(frame.clone());
+        Ok(())
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        let width = self.frames.iter().map(Frame::width).max().unwrap_or(0);
+        let height = self.frames.iter().map(Frame::height).max().unwrap_or(0);
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+
+        // gif sizes and delays (in hundredths of a second) are 16 bit.
+        let (Ok(image_width), Ok(image_height)) =
+            (u16::try_from(width * SCALE), u16::try_from(height * SCALE))
+        else {
+            return Err(io::Error::other(format!(
+                "frames of {width}x{height} cells are too large for a gif"
+            )));
+        };
+        let delay = u16::try_from(self.delay.as_millis() / 10).map_err(|_| {
+            io::Error::other(format!("a delay of {:?} is too long for a gif", self.delay))
+        })?;
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = BufWriter::new(File::create(&self.path)?);
+        let mut encoder =
+            gif::Encoder::new(file, image_width, image_height, &[]).map_err(io::Error::other)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(io::Error::other)?;
+
+        for frame in self.frames.drain(..) {
+            let data = frame.to_rgb(width, height, SCALE);
+            let mut image = gif::Frame::from_rgb_speed(image_width, image_height, &data, 10);
+            image.delay = delay;
+            encoder.write_frame(&image).map_err(io::Error::other)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Where `--visualize` sends the frames.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Target {
+    Terminal(Duration),
+    Frames(PathBuf, ImageFormat),
+    Gif(PathBuf),
+}
+
+impl Target {
+    /// Creates the sink for one part. Files get the part in their name, so both parts can be visualised at once.
+    fn sink(&self, part: u8) -> io::Result<Box<dyn Sink + Send>> {
+        Ok(match self {
+            Target::Terminal(delay) => Box::new(Terminal { delay: *delay }),
+            Target::Frames(dir, format) => {
+                Box::new(FrameDir::new(dir, &format!("part{part}"), *format)?)
+            }
+            Target::Gif(path) => {
+                let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+                let path = path.with_file_name(format!("{stem}-part{part}.gif"));
+                Box::new(Gif::new(&path, DEFAULT_DELAY))
+            }
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct TargetError(String);
+
+impl Display for TargetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "\"{}\" is not a visualisation target, expected terminal[:<delay ms>], ppm:<dir>, png:<dir> or gif:<file>.",
+            self.0
+        )
+    }
+}
+
+/// Parses `terminal`, `terminal:<delay ms>`, `ppm:<dir>`, `png:<dir>` and `gif:<file>`.
+impl FromStr for Target {
+    type Err = TargetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || TargetError(s.into());
+        let (kind, value) = match s.split_once(':') {
+            Some((kind, value)) => (kind, Some(value).filter(|value| !value.is_empty())),
+            None => (s, None),
+        };
+
+        match (kind, value) {
+            ("terminal", None) => Ok(Target::Terminal(DEFAULT_DELAY)),
+            ("terminal", Some(delay)) => delay
+                .parse()
+                .map(|delay| Target::Terminal(Duration::from_millis(delay)))
+                .map_err(|_| error()),
+            ("ppm", Some(dir)) => Ok(Target::Frames(dir.into(), ImageFormat::Ppm)),
+            ("png", Some(dir)) => Ok(Target::Frames(dir.into(), ImageFormat::Png)),
+            ("gif", Some(path)) => Ok(Target::Gif(path.into())),
+            _ => Err(error()),
+        }
+    }
+}
+
+/// Reads `--visualize` or `--visualize=<target>` from the arguments, defaulting to the terminal.
+fn target_from_args(args: impl Iterator<Item = String>) -> Option<Result<Target, TargetError>> {
+    args.take_while(|arg| arg != "--").find_map(|arg| {
+        if arg == "--visualize" {
+            Some(Ok(Target::Terminal(DEFAULT_DELAY)))
+        } else {
+            arg.strip_prefix("--visualize=").map(str::parse)
+        }
+    })
+}
+
+/// The sink of the part that is running, created with the first frame.
+struct State {
+    target: &'static Target,
+    part: u8,
+    sink: Option<Box<dyn Sink + Send>>,
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static TARGET: OnceLock<Option<Target>> = OnceLock::new();
+static STATE: Mutex<Option<State>> = Mutex::new(None);
+
+fn disable(message: impl Display) {
+    eprintln!("Visualisation disabled: {message}");
+    ENABLED.store(false, Ordering::Relaxed);
+}
+
+/// Whether a visualisation was requested with `--visualize`.
+#[inline]
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Sends a frame to the visualisation. `frame` is only called when `--visualize` is set,
+/// so solutions can emit frames freely without slowing down normal runs.
+#[inline]
+pub fn emit(frame: impl FnOnce() -> Frame) {
+    if enabled() {
+        send(frame());
+    }
+}
+
+fn send(frame: Frame) {
+    let mut state = STATE.lock().unwrap();
+    let Some(state) = state.as_mut() else {
+        return;
+    };
+
+    let sink = match &mut state.sink {
+        Some(sink) => sink,
+        None => match state.target.sink(state.part) {
+            Ok(sink) => state.sink.insert(sink),
+            Err(e) => return disable(e),
+        },
+    };
+
+    if let Err(e) = sink.frame(&frame) {
+        disable(e);
+    }
+}
+
+/// Prepares the visualisation of a part if `--visualize` was passed. Called by `solve!`.
+pub fn start(part: u8) {
+    let target = TARGET.get_or_init(|| match target_from_args(std::env::args().skip(1)) {
+        Some(Ok(target)) => Some(target),
+        Some(Err(e)) => {
+            eprintln!("{e}");
+            None
+        }
+        None => None,
+    });
+
+    if let Some(target) = target {
+        *STATE.lock().unwrap() = Some(State {
+            target,
+            part,
+            sink: None,
+        });
+        ENABLED.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Completes the visualisation of the current part. Called by `solve!`.
+pub fn finish() {
+    ENABLED.store(false, Ordering::Relaxed);
+    let state = STATE.lock().unwrap().take();
+    if let Some(mut sink) = state.and_then(|state| state.sink) {
+        if let Err(e) = sink.finish() {
+            eprintln!("Could not finish visualisation: {e}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target() {
+        assert_eq!("terminal".parse(), Ok(Target::Terminal(DEFAULT_DELAY)));
+        assert_eq!(
+            "terminal:20".parse(),
+            Ok(Target::Terminal(Duration::from_millis(20)))
+        );
+        assert_eq!(
+            "png:frames".parse(),
+            Ok(Target::Frames("frames".into(), ImageFormat::Png))
+        );
+        assert_eq!("gif:rope.gif".parse(), Ok(Target::Gif("rope.gif".into())));
+        assert!("gif".parse::<Target>().is_err());
+        assert!("terminal:fast".parse::<Target>().is_err());
+    }
+
+    #[test]
+    fn test_target_from_args() {
+        let args = |args: &[&str]| target_from_args(args.iter().map(|arg| arg.to_string()));
+        assert_eq!(args(&[]), None);
+        assert_eq!(
+            args(&["--visualize"]),
+            Some(Ok(Target::Terminal(DEFAULT_DELAY)))
+        );
+        assert_eq!(
+            args(&["--trail", "--visualize=ppm:out"]),
+            Some(Ok(Target::Frames("out".into(), ImageFormat::Ppm)))
+        );
+        assert_eq!(args(&["--", "--visualize"]), None);
+    }
+
+    #[test]
+    fn test_frame() {
+        let frame = Frame::text("#.\n#a#");
+        assert_eq!(frame.width(), 3);
+        assert_eq!(frame.height(), 2);
+        assert_eq!(frame.color(0, 0), Rgb::WHITE);
+        assert_eq!(frame.color(2, 0), Rgb::BACKGROUND);
+        assert_eq!(frame.color(5, 5), Rgb::BACKGROUND);
+        assert_eq!(frame.to_ansi(), "#. \n#a#");
+        assert_eq!(frame.to_rgb(3, 2, 2).len(), 3 * 2 * 4 * 3);
+    }
+
+    #[test]
+    fn test_files() {
+        let dir = std::env::temp_dir().join(format!("aoc-viz-{}", std::process::id()));
+        let mut sink = FrameDir::new(&dir, "part1", ImageFormat::Ppm).unwrap();
+        sink.frame(&Frame::text("#.")).unwrap();
+        sink.frame(&Frame::text("##")).unwrap();
+
+        let image = fs::read(dir.join("part1-0001.ppm")).unwrap();
+        let header = format!("P6\n{} {}\n255\n", 2 * SCALE, SCALE);
+        assert!(image.starts_with(header.as_bytes()));
+        assert_eq!(image.len(), header.len() + 2 * SCALE * SCALE * 3);
+
+        let path = dir.join("rope.gif");
+        let mut sink = Gif::new(&path, DEFAULT_DELAY);
+        sink.frame(&Frame::text("#")).unwrap();
+        sink.frame(&Frame::text("#.\n.#")).unwrap();
+        sink.finish().unwrap();
+        assert!(fs::read(&path).unwrap().starts_with(b"GIF89a"));
+
+        let mut sink = Gif::new(&dir.join("wide.gif"), DEFAULT_DELAY);
+        sink.frame(&Frame::text(&"#".repeat(u16::MAX as usize)))
+            .unwrap();
+        assert!(sink.finish().is_err());
+        let mut sink = Gif::new(&dir.join("slow.gif"), Duration::from_secs(1000));
+        sink.frame(&Frame::text("#")).unwrap();
+        assert!(sink.finish().is_err());
+        assert!(!dir.join("slow.gif").exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,408 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{
+    collections::HashMap,
+    env,
+    fmt::{Display, Write},
+    fs, io,
+    path::PathBuf,
+    process::Command,
+};
+use tinyjson::JsonValue;
+
+use crate::{config::Config, ANSI_BOLD, ANSI_RESET};
+
+#[derive(Debug)]
+pub enum LeaderboardError {
+    SessionNotFound,
+    CommandNotFound,
+    CommandNotCallable,
+    BadExitStatus(String),
+    InvalidJson(String),
+    IoError,
+}
+
+impl Display for LeaderboardError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LeaderboardError::SessionNotFound => {
+                write!(f, "could not read session cookie file.")
+            }
+            LeaderboardError::CommandNotFound => write!(
+                f,
+                "curl is not present in environment. Install it, or pass a saved leaderboard with --file."
+            ),
+            LeaderboardError::CommandNotCallable => write!(f, "curl could not be called."),
+            LeaderboardError::BadExitStatus(e) => write!(f, "request failed: {}", e.trim()),
+            LeaderboardError::InvalidJson(e) => write!(f, "could not parse leaderboard: {e}"),
+            LeaderboardError::IoError => write!(f, "could not read leaderboard file."),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DayProgress {
+    pub part_one: Option<u64>,
+    pub part_two: Option<u64>,
+}
+
+impl DayProgress {
+    pub fn stars(&self) -> u8 {
+        u8::from(self.part_one.is_some()) + u8::from(self.part_two.is_some())
+    }
+
+    /// Seconds it took to get part two after part one.
+    pub fn part_two_delta(&self) -> Option<u64> {
+        match (self.part_one, self.part_two) {
+            (Some(one), Some(two)) => Some(two.saturating_sub(one)),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    pub last_star_ts: u64,
+    pub days: [DayProgress; 25],
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leaderboard {
+    pub event: String,
+    pub owner_id: u64,
+    /// Members ranked by local score, ties broken by who got their last star first.
+    pub members: Vec<Member>,
+}
+
+fn field<'a>(
+    obj: &'a HashMap<String, JsonValue>,
+    key: &str,
+) -> Result<&'a JsonValue, LeaderboardError> {
+    obj.get(key)
+        .ok_or_else(|| LeaderboardError::InvalidJson(format!("missing field \"{key}\"")))
+}
+
+fn object(value: &JsonValue) -> Result<&HashMap<String, JsonValue>, LeaderboardError> {
+    value
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or_else(|| LeaderboardError::InvalidJson("expected an object".into()))
+}
+
+/// Numbers are sometimes sent as strings (e.g. `owner_id`), so accept both.
+fn number(value: &JsonValue) -> Result<u64, LeaderboardError> {
+    match value {
+        JsonValue::Number(n) => Ok(*n as u64),
+        JsonValue::String(s) => s
+            .parse()
+            .map_err(|_| LeaderboardError::InvalidJson(format!("\"{s}\" is not a number"))),
+        _ => Err(LeaderboardError::InvalidJson("expected a number".into())),
+    }
+}
+
+fn parse_member(value: &JsonValue) -> Result<Member, LeaderboardError> {
+    let obj = object(value)?;
+    let mut days = [DayProgress::default(); 25];
+
+    for (day, parts) in object(field(obj, "completion_day_level")?)? {
+        let index = day
+            .parse::<usize>()
+            .ok()
+            .filter(|day| (1..=25).contains(day))
+            .ok_or_else(|| LeaderboardError::InvalidJson(format!("invalid day \"{day}\"")))?;
+
+        for (part, star) in object(parts)? {
+            let ts = number(field(object(star)?, "get_star_ts")?)?;
+            match part.as_str() {
+                "1" => days[index - 1].part_one = Some(ts),
+                "2" => days[index - 1].part_two = Some(ts),
+                _ => {
+                    return Err(LeaderboardError::InvalidJson(format!(
+                        "invalid part \"{part}\""
+                    )))
+                }
+            }
+        }
+    }
+
+    Ok(Member {
+        id: number(field(obj, "id")?)?,
+        name: field(obj, "name")?.get::<String>().cloned(),
+        stars: number(field(obj, "stars")?)? as u32,
+        local_score: number(field(obj, "local_score")?)? as u32,
+        last_star_ts: number(field(obj, "last_star_ts")?)?,
+        days,
+    })
+}
+
+pub fn parse(json: &str) -> Result<Leaderboard, LeaderboardError> {
+    let value: JsonValue = json
+        .parse()
+        .map_err(|e: tinyjson::JsonParseError| LeaderboardError::InvalidJson(e.to_string()))?;
+    let obj = object(&value)?;
+
+    let mut members = object(field(obj, "members")?)?
+        .values()
+        .map(parse_member)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    members.sort_by(|a, b| {
+        b.local_score
+            .cmp(&a.local_score)
+            .then(a.last_star_ts.cmp(&b.last_star_ts))
+            .then(a.id.cmp(&b.id))
+    });
+
+    Ok(Leaderboard {
+        event: field(obj, "event")?
+            .get::<String>()
+            .cloned()
+            .unwrap_or_default(),
+        owner_id: number(field(obj, "owner_id")?)?,
+        members,
+    })
+}
+
+/// Formats a duration in the two most significant units, e.g. `4m07s` or `1d02h`.
+pub fn format_duration(secs: u64) -> String {
+    let (days, hours, mins, secs) = (secs / 86400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+    if days > 0 {
+        format!("{days}d{hours:02}h")
+    } else if hours > 0 {
+        format!("{hours}h{mins:02}m")
+    } else if mins > 0 {
+        format!("{mins}m{secs:02}s")
+    } else {
+        format!("{secs}s")
+    }
+}
+
+fn star_glyph(progress: &DayProgress) -> char {
+    match progress.stars() {
+        2 => '★',
+        1 => '☆',
+        _ => '·',
+    }
+}
+
+pub fn render(leaderboard: &Leaderboard) -> String {
+    let mut out = String::new();
+    let rank_width = leaderboard.members.len().to_string().len();
+    let score_width = leaderboard
+        .members
+        .iter()
+        .map(|m| m.local_score.to_string().len())
+        .max()
+        .unwrap_or(1);
+    let padding = " ".repeat(rank_width + score_width + 3);
+
+    let tens: String = (1..=25)
+        .map(|d| {
+            if d >= 10 {
+                char::from(b'0' + d / 10)
+            } else {
+                ' '
+            }
+        })
+        .collect();
+    let ones: String = (1..=25).map(|d| char::from(b'0' + d % 10)).collect();
+    writeln!(
+        out,
+        "{ANSI_BOLD}Leaderboard {}{ANSI_RESET}",
+        leaderboard.event
+    )
+    .unwrap();
+    writeln!(out, "{padding}{}", tens.trim_end()).unwrap();
+    writeln!(out, "{padding}{ones}").unwrap();
+
+    for (rank, member) in leaderboard.members.iter().enumerate() {
+        let stars: String = member.days.iter().map(star_glyph).collect();
+        writeln!(
+            out,
+            "{:>rank_width$}) {:>score_width$} {} {}",
+            rank + 1,
+            member.local_score,
+            stars,
+            member.display_name()
+        )
+        .unwrap();
+    }
+
+    let days: Vec<usize> = (0..25)
+        .filter(|&d| {
+            leaderboard
+                .members
+                .iter()
+                .any(|m| m.days[d].part_two_delta().is_some())
+        })
+        .collect();
+
+    if days.is_empty() {
+        return out;
+    }
+
+    let name_width = leaderboard
+        .members
+        .iter()
+        .map(|m| m.display_name().chars().count())
+        .max()
+        .unwrap_or(0);
+
+    writeln!(out).unwrap();
+    writeln!(out, "{ANSI_BOLD}Time from part one to part two{ANSI_RESET}").unwrap();
+    write!(out, "{:name_width$}", "").unwrap();
+    for day in &days {
+        write!(out, " {:>7}", format!("Day {}", day + 1)).unwrap();
+    }
+    writeln!(out).unwrap();
+
+    for member in &leaderboard.members {
+        write!(out, "{:name_width$}", member.display_name()).unwrap();
+        for &day in &days {
+            let cell = member.days[day]
+                .part_two_delta()
+                .map_or_else(|| "-".into(), format_duration);
+            write!(out, " {cell:>7}").unwrap();
+        }
+        writeln!(out).unwrap();
+    }
+
+    out
+}
+
+fn session_path() -> Option<PathBuf> {
+    if let Some(path) = &Config::get().session_file {
+        return Some(path.clone());
+    }
+
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(|home| PathBuf::from(home).join(".adventofcode.session"))
+}
+
+pub fn read_local(path: &str) -> Result<String, LeaderboardError> {
+    fs::read_to_string(path).map_err(|_| LeaderboardError::IoError)
+}
+
+/// Sent with every request, so Advent of Code can tell where the traffic comes from.
+const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (cargo leaderboard)"
+);
+
+/// Fetches the leaderboard JSON with curl, authenticated by the session cookie aoc-cli uses.
+/// aoc-cli can only print private leaderboards as a table, not the JSON behind them.
+pub fn fetch(id: u64, year: u16) -> Result<String, LeaderboardError> {
+    let session = session_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .map(|session| session.trim().to_string())
+        .filter(|session| !session.is_empty())
+        .ok_or(LeaderboardError::SessionNotFound)?;
+
+    let url = format!("https://adventofcode.com/{year}/leaderboard/private/view/{id}.json");
+
+    let output = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--location"])
+        .args(["--user-agent", USER_AGENT])
+        .args(["--cookie", &format!("session={session}")])
+        .arg(&url)
+        .output()
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => LeaderboardError::CommandNotFound,
+            _ => LeaderboardError::CommandNotCallable,
+        })?;
+
+    if !output.status.success() {
+        return Err(LeaderboardError::BadExitStatus(
+            String::from_utf8_lossy(&output.stderr).into(),
+        ));
+    }
+
+    String::from_utf8(output.stdout).map_err(|e| LeaderboardError::InvalidJson(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r#"{
+        "owner_id": 1,
+        "event": "2022",
+        "members": {
+            "1": {
+                "id": 1, "name": "alice", "stars": 3, "local_score": 10, "global_score": 0,
+                "last_star_ts": 1670000300,
+                "completion_day_level": {
+                    "1": { "1": { "get_star_ts": 1669870000, "star_index": 1 },
+                           "2": { "get_star_ts": 1669870247, "star_index": 2 } },
+                    "2": { "1": { "get_star_ts": 1670000300, "star_index": 3 } }
+                }
+            },
+            "2": {
+                "id": 2, "name": null, "stars": 2, "local_score": 12, "global_score": 0,
+                "last_star_ts": 1669960000,
+                "completion_day_level": {
+                    "1": { "1": { "get_star_ts": 1669869000, "star_index": 1 },
+                           "2": { "get_star_ts": 1669960000, "star_index": 2 } }
+                }
+            },
+            "3": {
+                "id": 3, "name": "carol", "stars": 0, "local_score": 0, "global_score": 0,
+                "last_star_ts": 0, "completion_day_level": {}
+            }
+        }
+    }"#;
+
+    #[test]
+    fn test_parse() {
+        let leaderboard = parse(EXAMPLE).unwrap();
+        assert_eq!(leaderboard.event, "2022");
+        assert_eq!(
+            leaderboard.members.iter().map(|m| m.id).collect::<Vec<_>>(),
+            vec![2, 1, 3]
+        );
+
+        let alice = &leaderboard.members[1];
+        assert_eq!(alice.days[0].part_two_delta(), Some(247));
+        assert_eq!(alice.days[1].stars(), 1);
+        assert_eq!(alice.days[1].part_two_delta(), None);
+        assert_eq!(leaderboard.members[0].display_name(), "(anonymous user #2)");
+    }
+
+    #[test]
+    fn test_render() {
+        let rendered = render(&parse(EXAMPLE).unwrap());
+        let dots = |n| "·".repeat(n);
+        assert!(rendered.contains(&format!("1) 12 ★{} (anonymous user #2)", dots(24))));
+        assert!(rendered.contains(&format!("2) 10 ★☆{} alice", dots(23))));
+        assert!(rendered.contains(&format!("3)  0 {} carol", dots(25))));
+        assert!(rendered.contains("                      Day 1\n"));
+        assert!(rendered.contains("(anonymous user #2)   1d01h\n"));
+        assert!(rendered.contains("alice                 4m07s\n"));
+        assert!(rendered.contains("carol                     -\n"));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(42), "42s");
+        assert_eq!(format_duration(247), "4m07s");
+        assert_eq!(format_duration(3 * 3600 + 61), "3h01m");
+        assert_eq!(format_duration(2 * 86400 + 3600), "2d01h");
+    }
+}
@@ -3,10 +3,25 @@
  * There is no need to edit this file unless you want to change template functionality.
  * Prefer `./helpers.rs` if you want to extract code from your solutions.
  */
-use std::env;
-use std::fs;
-
+use config::Config;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, Cursor};
+use std::path::{Path, PathBuf};
+
+pub mod aoc_cli;
+pub mod calendar;
+pub mod config;
+pub mod day;
 pub mod helpers;
+pub mod leaderboard;
+pub mod progress;
+pub mod runner;
+pub mod scaffold;
+pub mod unlock;
+pub mod variants;
+pub mod vault;
+
+pub use day::Day;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -19,35 +34,127 @@ macro_rules! solve {
         use std::fmt::Display;
         use std::time::Instant;
 
-        fn print_result<T: Display>(func: impl FnOnce(&str) -> Option<T>, input: &str) {
+        /// Prints the answer and returns it, with block letters decoded.
+        fn print_result<I, T: Display>(
+            func: impl FnOnce(I) -> Option<T>,
+            input: I,
+        ) -> Option<String> {
             let timer = Instant::now();
             let result = func(input);
             let elapsed = timer.elapsed();
-            match result {
+            let answer = match result {
                 Some(result) => {
+                    // block letters are printed as drawn, followed by the text they spell as the answer.
+                    let result = result.to_string();
+                    let answer = match advent_of_code::helpers::ocr::decode(&result) {
+                        Some(letters) => {
+                            println!("{}", result);
+                            letters
+                        }
+                        None => result,
+                    };
                     println!(
                         "{} {}(elapsed: {:.2?}){}",
-                        result, ANSI_ITALIC, elapsed, ANSI_RESET
+                        answer, ANSI_ITALIC, elapsed, ANSI_RESET
                     );
+                    Some(answer)
                 }
                 None => {
-                    println!("not solved.")
+                    println!("not solved.");
+                    None
                 }
+            };
+            if let Some(stats) = advent_of_code::helpers::memo::take_stats() {
+                println!("{}memo: {}{}", ANSI_ITALIC, stats, ANSI_RESET);
             }
+            answer
         }
 
+        advent_of_code::helpers::viz::start($part);
         println!("🎄 {}Part {}{} 🎄", ANSI_BOLD, $part, ANSI_RESET);
-        print_result($solver, $input);
+        if let Some(answer) = print_result($solver, $input) {
+            advent_of_code::aoc_cli::submit_if_requested(env!("CARGO_BIN_NAME"), $part, &answer);
+        }
+        advent_of_code::helpers::viz::finish();
+    }};
+    // named implementations of a part. The first one runs by default, `--all-variants` runs and compares all of them.
+    ($part:expr, [$first_name:literal => $first:ident $(, $name:literal => $solver:ident)* $(,)?], $input:expr) => {{
+        if advent_of_code::variants::all_variants() {
+            use advent_of_code::{variants, ANSI_BOLD, ANSI_RESET};
+
+            println!("🎄 {}Part {}{} 🎄", ANSI_BOLD, $part, ANSI_RESET);
+            let reports = variants::benchmark(
+                &[($first_name, $first as fn(_) -> _) $(, ($name, $solver as fn(_) -> _))*],
+                || $input,
+            );
+            advent_of_code::helpers::memo::take_stats();
+            print!("{}", variants::format_reports(&reports));
+
+            let disagreeing = variants::disagreeing(&reports);
+            if !disagreeing.is_empty() {
+                eprintln!(
+                    "Variants disagree with {}: {}",
+                    reports[0].name,
+                    disagreeing.join(", ")
+                );
+                std::process::exit(1);
+            }
+        } else {
+            advent_of_code::solve!($part, $first, $input);
+        }
     }};
 }
 
-pub fn read_file(folder: &str, day: u8) -> String {
-    let cwd = env::current_dir().unwrap();
+/// Contents of the file for a day, either on disk or decrypted from its sealed copy.
+enum Source {
+    File(PathBuf),
+    Unsealed(String),
+}
 
-    let filepath = cwd.join("src").join(folder).join(format!("{day:02}.txt"));
+/// The sealed copy (`NN.txt.enc`) to read instead of `filepath` if that is missing or empty.
+/// Like `cargo vault`, this treats an empty input as a missing one.
+fn sealed_fallback(filepath: &Path) -> Option<PathBuf> {
+    let sealed_path = vault::sealed_path(filepath);
+    let is_empty = fs::metadata(filepath).map_or(true, |meta| meta.len() == 0);
+    (is_empty && sealed_path.exists()).then_some(sealed_path)
+}
+
+/// Finds the file for `day` in `folder`. A sealed copy is decrypted with the vault key.
+fn source(folder: &str, day: Day) -> Source {
+    let filepath = day.path(&Config::get().folder(folder), "txt");
 
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    if let Some(sealed_path) = sealed_fallback(&filepath) {
+        return Source::Unsealed(
+            vault::Vault::load()
+                .and_then(|vault| vault.unseal_file(&sealed_path))
+                .unwrap_or_else(|e| panic!("could not unseal input file: {e}")),
+        );
+    }
+
+    Source::File(filepath)
+}
+
+/// Reads the file for `day` from `folder`, or decrypts its sealed copy.
+pub fn read_file(folder: &str, day: Day) -> String {
+    match source(folder, day) {
+        Source::File(filepath) => {
+            let f = fs::read_to_string(filepath);
+            f.expect("could not open input file")
+        }
+        Source::Unsealed(input) => input,
+    }
+}
+
+/// Opens the file for `day` in `folder` to be read line by line, without loading all of it into memory.
+/// Sealed copies are decrypted into memory first.
+pub fn open_file(folder: &str, day: Day) -> Box<dyn BufRead> {
+    match source(folder, day) {
+        Source::File(filepath) => {
+            let f = File::open(filepath);
+            Box::new(BufReader::new(f.expect("could not open input file")))
+        }
+        Source::Unsealed(input) => Box::new(Cursor::new(input)),
+    }
 }
 
 fn parse_time(val: &str, postfix: &str) -> f64 {
@@ -95,6 +202,24 @@ macro_rules! assert_approx_eq {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_sealed_fallback() {
+        let dir = std::env::temp_dir().join(format!("aoc-sealed-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("01.txt");
+        let sealed = vault::sealed_path(&input);
+
+        assert_eq!(sealed_fallback(&input), None);
+        fs::write(&sealed, "sealed").unwrap();
+        assert_eq!(sealed_fallback(&input), Some(sealed.clone()));
+        fs::write(&input, "").unwrap();
+        assert_eq!(sealed_fallback(&input), Some(sealed));
+        fs::write(&input, "1").unwrap();
+        assert_eq!(sealed_fallback(&input), None);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
     #[test]
     fn test_parse_exec_time() {
         assert_approx_eq!(
@@ -123,112 +248,3 @@ mod tests {
         );
     }
 }
-
-pub mod aoc_cli {
-    use std::{
-        fmt::Display,
-        fs::create_dir_all,
-        process::{Command, Output, Stdio},
-    };
-
-    pub enum AocCliError {
-        CommandNotFound,
-        CommandNotCallable,
-        BadExitStatus(Output),
-        IoError,
-    }
-
-    impl Display for AocCliError {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            match self {
-                AocCliError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
-                AocCliError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
-                AocCliError::BadExitStatus(_) => {
-                    write!(f, "aoc-cli exited with a non-zero status.")
-                }
-                AocCliError::IoError => write!(f, "could not write output files to file system."),
-            }
-        }
-    }
-
-    pub fn check() -> Result<(), AocCliError> {
-        Command::new("aoc")
-            .arg("-V")
-            .output()
-            .map_err(|_| AocCliError::CommandNotFound)?;
-        Ok(())
-    }
-
-    pub fn read(day: u8, year: Option<u16>) -> Result<Output, AocCliError> {
-        // TODO: output local puzzle if present.
-        let args = build_args("read", &[], day, year);
-        call_aoc_cli(&args)
-    }
-
-    pub fn download(day: u8, year: Option<u16>) -> Result<Output, AocCliError> {
-        let input_path = get_input_path(day);
-
-        let puzzle_path = get_puzzle_path(day);
-        create_dir_all("src/puzzles").map_err(|_| AocCliError::IoError)?;
-
-        let args = build_args(
-            "download",
-            &[
-                "--overwrite".into(),
-                "--input-file".into(),
-                input_path.to_string(),
-                "--puzzle-file".into(),
-                puzzle_path.to_string(),
-            ],
-            day,
-            year,
-        );
-
-        let output = call_aoc_cli(&args)?;
-
-        if output.status.success() {
-            println!("---");
-            println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-            println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-            Ok(output)
-        } else {
-            Err(AocCliError::BadExitStatus(output))
-        }
-    }
-
-    fn get_input_path(day: u8) -> String {
-        let day_padded = format!("{day:02}");
-        format!("src/inputs/{day_padded}.txt")
-    }
-
-    fn get_puzzle_path(day: u8) -> String {
-        let day_padded = format!("{day:02}");
-        format!("src/puzzles/{day_padded}.md")
-    }
-
-    fn build_args(command: &str, args: &[String], day: u8, year: Option<u16>) -> Vec<String> {
-        let mut cmd_args = args.to_vec();
-
-        if let Some(year) = year {
-            cmd_args.push("--year".into());
-            cmd_args.push(year.to_string());
-        }
-
-        cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
-
-        cmd_args
-    }
-
-    fn call_aoc_cli(args: &[String]) -> Result<Output, AocCliError> {
-        if cfg!(debug_assertions) {
-            println!("Calling >aoc with: {}", args.join(" "));
-        }
-
-        Command::new("aoc")
-            .args(args)
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .output()
-            .map_err(|_| AocCliError::CommandNotCallable)
-    }
-}
@@ -2,44 +2,151 @@
  * This file contains template code.
  * There is no need to edit this file unless you want to change template functionality.
  */
-use advent_of_code::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-use std::process::Command;
+use advent_of_code::config::{Config, OutputFormat};
+use advent_of_code::runner::{self, Outcome};
+use advent_of_code::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use std::collections::BTreeMap;
+use std::process;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use tinyjson::JsonValue;
+
+struct Args {
+    release: bool,
+    timeout: Option<u64>,
+    jobs: usize,
+    format: OutputFormat,
+}
+
+/// Command line flags take precedence over the `[runner]` section of the config file.
+fn parse_args() -> Result<Args, pico_args::Error> {
+    let runner = &Config::get().runner;
+    let mut args = pico_args::Arguments::from_env();
+
+    let release = if args.contains("--release") {
+        true
+    } else if args.contains("--debug") {
+        false
+    } else {
+        runner.release.unwrap_or(cfg!(not(debug_assertions)))
+    };
+
+    Ok(Args {
+        release,
+        timeout: args.opt_value_from_str("--timeout")?.or(runner.timeout),
+        jobs: args
+            .opt_value_from_str(["-j", "--jobs"])?
+            .unwrap_or(runner.jobs)
+            .max(1),
+        format: args
+            .opt_value_from_str("--format")?
+            .unwrap_or(runner.format),
+    })
+}
+
+fn print_text(day: Day, outcome: &Outcome, timeout: Option<u64>) {
+    println!("----------");
+    println!("{ANSI_BOLD}| Day {day} |{ANSI_RESET}");
+    println!("----------");
+
+    match outcome {
+        Outcome::Solved(output) => println!("{}", output.trim()),
+        Outcome::NotSolved => println!("Not solved."),
+        Outcome::TimedOut => println!("Timed out after {}s.", timeout.unwrap_or_default()),
+    }
+}
+
+fn json_day(day: Day, outcome: &Outcome) -> String {
+    let (status, output) = match outcome {
+        Outcome::Solved(output) => ("solved", Some(output.trim())),
+        Outcome::NotSolved => ("not_solved", None),
+        Outcome::TimedOut => ("timed_out", None),
+    };
+    let elapsed = match outcome {
+        Outcome::Solved(output) => advent_of_code::parse_exec_time(output),
+        _ => 0_f64,
+    };
+    let output = output.map_or_else(
+        || "null".into(),
+        |output| {
+            JsonValue::String(output.to_string())
+                .stringify()
+                .unwrap_or_default()
+        },
+    );
+
+    format!(
+        "{{\"day\":{},\"status\":\"{status}\",\"elapsed_ms\":{elapsed:.3},\"output\":{output}}}",
+        day.into_inner()
+    )
+}
 
 fn main() {
-    let total: f64 = (1..=25)
-        .map(|day| {
-            let day = format!("{day:02}");
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("Failed to process arguments: {e}");
+            process::exit(1);
+        }
+    };
 
-            let mut args = vec!["run", "--bin", &day];
-            if cfg!(not(debug_assertions)) {
-                args.push("--release");
-            }
+    let solutions = runner::solution_days();
 
-            let cmd = Command::new("cargo").args(&args).output().unwrap();
+    if !runner::build_solutions(&solutions, args.release) {
+        eprintln!("Failed to build solutions.");
+        process::exit(1);
+    }
 
-            println!("----------");
-            println!("{ANSI_BOLD}| Day {day} |{ANSI_RESET}");
-            println!("----------");
+    let next_day = AtomicU8::new(1);
+    let (sender, receiver) = mpsc::channel();
 
-            let output = String::from_utf8(cmd.stdout).unwrap();
-            let is_empty = output.is_empty();
+    let mut total = 0_f64;
+    let mut json_days = vec![];
 
-            println!(
-                "{}",
-                if is_empty {
-                    "Not solved."
-                } else {
-                    output.trim()
+    thread::scope(|s| {
+        for _ in 0..args.jobs {
+            let sender = sender.clone();
+            let (next_day, args, solutions) = (&next_day, &args, &solutions);
+            s.spawn(move || {
+                while let Some(day) = Day::new(next_day.fetch_add(1, Ordering::SeqCst)) {
+                    let outcome = if solutions.contains(&day) {
+                        runner::run_solution(day, args.release, args.timeout)
+                    } else {
+                        Outcome::NotSolved
+                    };
+                    sender.send((day, outcome)).unwrap();
                 }
-            );
+            });
+        }
+        drop(sender);
 
-            if is_empty {
-                0_f64
-            } else {
-                advent_of_code::parse_exec_time(&output)
+        // results arrive out of order with parallel jobs, print them in order of days.
+        let mut pending = BTreeMap::new();
+        let mut days = Day::all();
+        let mut next = days.next();
+        for (day, outcome) in receiver {
+            pending.insert(day, outcome);
+            while let Some((day, outcome)) = next.and_then(|next| pending.remove_entry(&next)) {
+                if let Outcome::Solved(output) = &outcome {
+                    total += advent_of_code::parse_exec_time(output);
+                }
+                match args.format {
+                    OutputFormat::Text => print_text(day, &outcome, args.timeout),
+                    OutputFormat::Json => json_days.push(json_day(day, &outcome)),
+                }
+                next = days.next();
             }
-        })
-        .sum();
+        }
+    });
 
-    println!("{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total:.2}ms{ANSI_RESET}");
+    match args.format {
+        OutputFormat::Text => {
+            println!("{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total:.2}ms{ANSI_RESET}")
+        }
+        OutputFormat::Json => println!(
+            "{{\"days\":[{}],\"total_ms\":{total:.3}}}",
+            json_days.join(",")
+        ),
+    }
 }
@@ -0,0 +1,169 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::fmt::Write;
+
+use crate::{Day, ANSI_BOLD, ANSI_RESET};
+
+/// Marker comments around the star table in the readme, as used by the `readme-stars` workflow.
+pub const README_MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// Solved parts per day.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Progress {
+    pub days: [[bool; 2]; 25],
+}
+
+impl Progress {
+    pub fn stars(&self) -> usize {
+        self.days.iter().flatten().filter(|solved| **solved).count()
+    }
+}
+
+/// Finds which parts printed an answer in the output of `solve!`. A part is unsolved if it returned `None`.
+pub fn parse_solved(output: &str) -> [bool; 2] {
+    let mut solved = [false; 2];
+    let mut part = None;
+
+    for line in output.lines() {
+        if line.starts_with('🎄') && line.contains("Part 1") {
+            part = Some(0);
+        } else if line.starts_with('🎄') && line.contains("Part 2") {
+            part = Some(1);
+        } else if let Some(i) = part {
+            // answers can span several lines, the timing is printed after the last one.
+            if line.contains("(elapsed:") {
+                solved[i] = true;
+                part = None;
+            }
+        }
+    }
+
+    solved
+}
+
+fn stars(parts: &[bool; 2]) -> &'static str {
+    match parts {
+        [true, true] => "★★",
+        [true, false] => "★☆",
+        [false, true] => "☆★",
+        [false, false] => "··",
+    }
+}
+
+/// Renders the advent calendar as a grid of five weeks.
+pub fn render_calendar(progress: &Progress, year: u16) -> String {
+    let mut out = String::new();
+    writeln!(out, "{ANSI_BOLD}🎄 Advent of Code {year} 🎄{ANSI_RESET}").unwrap();
+    writeln!(out).unwrap();
+
+    for (week, days) in progress.days.chunks(5).enumerate() {
+        let row: Vec<String> = days
+            .iter()
+            .enumerate()
+            .map(|(i, parts)| format!("{:>2} {}", week * 5 + i + 1, stars(parts)))
+            .collect();
+        writeln!(out, "{}", row.join("   ")).unwrap();
+    }
+
+    writeln!(out).unwrap();
+    writeln!(out, "{ANSI_BOLD}Stars:{ANSI_RESET} {}/50", progress.stars()).unwrap();
+    out
+}
+
+/// Renders the readme star table in the format of the `readme-stars` workflow, including the markers.
+pub fn render_readme_table(progress: &Progress, year: u16) -> String {
+    let mut out = String::new();
+    writeln!(out, "{README_MARKER}").unwrap();
+    writeln!(out, "## {year} Results").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "| Day | Part 1 | Part 2 |").unwrap();
+    writeln!(out, "| :---: | :---: | :---: |").unwrap();
+
+    for (day, parts) in Day::all().zip(&progress.days) {
+        if !parts.iter().any(|solved| *solved) {
+            continue;
+        }
+        let star = |solved: bool| if solved { "⭐" } else { " " };
+        writeln!(
+            out,
+            "| [Day {}]({}) | {} | {} |",
+            day.into_inner(),
+            day.url(year),
+            star(parts[0]),
+            star(parts[1]),
+        )
+        .unwrap();
+    }
+
+    write!(out, "{README_MARKER}").unwrap();
+    out
+}
+
+/// Replaces the star table between the markers in `readme`. Returns `None` if the markers are missing.
+pub fn update_readme(readme: &str, table: &str) -> Option<String> {
+    let start = readme.find(README_MARKER)?;
+    let end = readme[start + README_MARKER.len()..].find(README_MARKER)?
+        + start
+        + README_MARKER.len() * 2;
+
+    Some(format!("{}{}{}", &readme[..start], table, &readme[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_solved() {
+        let output = "🎄 \x1b[1mPart 1\x1b[0m 🎄\n24000 \x1b[3m(elapsed: 45.73µs)\x1b[0m\n🎄 \x1b[1mPart 2\x1b[0m 🎄\nnot solved.\n";
+        assert_eq!(parse_solved(output), [true, false]);
+
+        let output = "🎄 Part 1 🎄\nnot solved.\n🎄 Part 2 🎄\n##..\n..## (elapsed: 1.00ms)\n";
+        assert_eq!(parse_solved(output), [false, true]);
+
+        assert_eq!(parse_solved(""), [false, false]);
+    }
+
+    #[test]
+    fn test_render_readme_table() {
+        let mut progress = Progress::default();
+        progress.days[0] = [true, true];
+        progress.days[2] = [true, false];
+
+        assert_eq!(
+            render_readme_table(&progress, 2022),
+            "<!--- advent_readme_stars table --->
+## 2022 Results
+
+| Day | Part 1 | Part 2 |
+| :---: | :---: | :---: |
+| [Day 1](https://adventofcode.com/2022/day/1) | ⭐ | ⭐ |
+| [Day 3](https://adventofcode.com/2022/day/3) | ⭐ |   |
+<!--- advent_readme_stars table --->"
+        );
+    }
+
+    #[test]
+    fn test_update_readme() {
+        let readme = format!("# AoC\n{README_MARKER}\nold\n{README_MARKER}\n## Setup\n");
+        assert_eq!(
+            update_readme(&readme, "new").unwrap(),
+            "# AoC\nnew\n## Setup\n"
+        );
+        assert_eq!(update_readme("# AoC\n", "new"), None);
+    }
+
+    #[test]
+    fn test_render_calendar() {
+        let mut progress = Progress::default();
+        progress.days[0] = [true, true];
+        progress.days[6] = [true, false];
+
+        let calendar = render_calendar(&progress, 2022);
+        assert!(calendar.contains(" 1 ★★    2 ··    3 ··    4 ··    5 ··\n"));
+        assert!(calendar.contains(" 6 ··    7 ★☆    8 ··"));
+        assert!(calendar.contains("3/50"));
+    }
+}
@@ -0,0 +1,102 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{
+    io::Read,
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{config::Config, Day};
+
+pub enum Outcome {
+    Solved(String),
+    NotSolved,
+    TimedOut,
+}
+
+fn cargo_args(command: &str, release: bool) -> Vec<&str> {
+    let mut args = vec![command, "--quiet"];
+    if release {
+        args.push("--release");
+    }
+    args
+}
+
+/// Days that have a solution module.
+pub fn solution_days() -> Vec<Day> {
+    let bin_dir = &Config::get().paths.bin;
+    Day::all()
+        .filter(|day| day.path(bin_dir, "rs").is_file())
+        .collect()
+}
+
+/// Builds the solutions for `days` upfront.
+/// This keeps build time from counting towards a timeout and keeps parallel runs from racing for the build lock.
+pub fn build_solutions(days: &[Day], release: bool) -> bool {
+    if days.is_empty() {
+        return true;
+    }
+
+    let bins: Vec<String> = days.iter().map(Day::to_string).collect();
+    let mut args = cargo_args("build", release);
+    for bin in &bins {
+        args.extend(["--bin", bin]);
+    }
+
+    Command::new("cargo")
+        .args(&args)
+        .stdout(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Runs the solution for `day` and captures its output. The solution is killed after `timeout` seconds.
+pub fn run_solution(day: Day, release: bool, timeout: Option<u64>) -> Outcome {
+    let day = day.to_string();
+    let mut cmd_args = cargo_args("run", release);
+    cmd_args.extend(["--bin", &day]);
+
+    let mut child = match Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(_) => return Outcome::NotSolved,
+    };
+
+    // read on a separate thread so a chatty solution can't block on a full pipe while we wait for it.
+    let mut stdout = child.stdout.take().unwrap();
+    let reader = thread::spawn(move || {
+        let mut output = String::new();
+        stdout.read_to_string(&mut output).ok();
+        output
+    });
+
+    let deadline = timeout.map(|secs| Instant::now() + Duration::from_secs(secs));
+
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if deadline.is_some_and(|deadline| Instant::now() >= deadline) => {
+                child.kill().ok();
+                child.wait().ok();
+                return Outcome::TimedOut;
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(5)),
+            Err(_) => return Outcome::NotSolved,
+        }
+    }
+
+    let output = reader.join().unwrap_or_default();
+
+    if output.is_empty() {
+        Outcome::NotSolved
+    } else {
+        Outcome::Solved(output)
+    }
+}
@@ -0,0 +1,503 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{config::Config, vault, Day};
+
+/// Kind used when `--template` is not passed.
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+/// Fallback for the default kind, in case the templates directory was removed.
+const BUILTIN_TEMPLATE: &str = include_str!("../templates/default.rs.tmpl");
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum TemplateError {
+    NotFound(String, Vec<String>),
+    UnknownPlaceholder(String, usize),
+    Unclosed(usize),
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateError::NotFound(kind, available) => write!(
+                f,
+                "template \"{kind}\" not found. Available templates: {}.",
+                available.join(", ")
+            ),
+            TemplateError::UnknownPlaceholder(name, line) => {
+                write!(f, "unknown placeholder \"{{{{{name}}}}}\" on line {line}.")
+            }
+            TemplateError::Unclosed(line) => write!(f, "unclosed placeholder on line {line}."),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DaysError {
+    Invalid(String),
+    OutOfRange(u8),
+    Empty(u8, u8),
+}
+
+impl Display for DaysError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DaysError::Invalid(spec) => write!(
+                f,
+                "\"{spec}\" is not a day or a range of days. example: `7` or `12..25`."
+            ),
+            DaysError::OutOfRange(day) => write!(f, "day {day} is not between 1 and 25."),
+            DaysError::Empty(from, to) => write!(f, "range {from}..{to} contains no days."),
+        }
+    }
+}
+
+/// Parses a single day (`7`) or an inclusive range of days (`12..25` or `12..=25`).
+pub fn parse_days(spec: &str) -> Result<Vec<Day>, DaysError> {
+    let parse_day = |s: &str| -> Result<Day, DaysError> {
+        let day = s
+            .trim()
+            .parse()
+            .map_err(|_| DaysError::Invalid(spec.into()))?;
+        Day::new(day).ok_or(DaysError::OutOfRange(day))
+    };
+
+    match spec.split_once("..") {
+        Some((from, to)) => {
+            let (from, to) = (parse_day(from)?, parse_day(to.trim_start_matches('='))?);
+            if from > to {
+                return Err(DaysError::Empty(from.into_inner(), to.into_inner()));
+            }
+            Ok(Day::all()
+                .skip_while(|day| *day < from)
+                .take_while(|day| *day <= to)
+                .collect())
+        }
+        None => Ok(vec![parse_day(spec)?]),
+    }
+}
+
+/// Files that make up a scaffolded day.
+pub struct DayPaths {
+    pub module: PathBuf,
+    pub input: PathBuf,
+    pub example: PathBuf,
+    pub puzzle: PathBuf,
+}
+
+impl DayPaths {
+    pub fn new(day: Day) -> Self {
+        let paths = &Config::get().paths;
+        Self {
+            module: day.path(&paths.bin, "rs"),
+            input: day.path(&paths.inputs, "txt"),
+            example: day.path(&paths.examples, "txt"),
+            puzzle: day.path(&paths.puzzles, "md"),
+        }
+    }
+}
+
+/// A single action taken when scaffolding a day.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Step {
+    Download,
+    CreateModule(PathBuf),
+    /// Moves the existing module to the backup path before generating a new one.
+    ReplaceModule(PathBuf, PathBuf),
+    SkipModule(PathBuf),
+    CreateFile(PathBuf),
+    /// Fills the example from the downloaded puzzle, or creates it empty if no example is found.
+    ExtractExample(PathBuf),
+    /// Keeps an input or example file that already has content.
+    KeepFile(PathBuf),
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let config = Config::get();
+        match self {
+            Step::Download => write!(f, "download input and puzzle"),
+            Step::CreateModule(path) => {
+                write!(f, "create module \"{}\"", config.display_path(path))
+            }
+            Step::ReplaceModule(path, backup) => write!(
+                f,
+                "back up module \"{}\" to \"{}\" and regenerate it",
+                config.display_path(path),
+                config.display_path(backup)
+            ),
+            Step::SkipModule(path) => write!(
+                f,
+                "skip existing module \"{}\" (pass --force to regenerate it)",
+                config.display_path(path)
+            ),
+            Step::CreateFile(path) => {
+                write!(f, "create empty file \"{}\"", config.display_path(path))
+            }
+            Step::ExtractExample(path) => {
+                write!(f, "extract example to \"{}\"", config.display_path(path))
+            }
+            Step::KeepFile(path) => {
+                write!(f, "keep existing file \"{}\"", config.display_path(path))
+            }
+        }
+    }
+}
+
+fn is_non_empty_file(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|meta| meta.len() > 0)
+}
+
+/// First free backup path for `path`: `01.rs.bak`, then `01.rs.bak.1`, `01.rs.bak.2`...
+pub fn backup_path(path: &Path) -> PathBuf {
+    let mut backup = path.as_os_str().to_owned();
+    backup.push(".bak");
+
+    let numbered = (1..).map(|n| {
+        let mut numbered = backup.clone();
+        numbered.push(format!(".{n}"));
+        numbered
+    });
+
+    std::iter::once(backup.clone())
+        .chain(numbered)
+        .map(PathBuf::from)
+        .find(|candidate| !candidate.exists())
+        .unwrap()
+}
+
+/// Plans the steps to scaffold a day based on the files that already exist.
+///
+/// Non-empty inputs and examples are never touched, and an existing module is only replaced with `force`.
+pub fn plan(paths: &DayPaths, download: bool, force: bool) -> Vec<Step> {
+    let mut steps = vec![];
+
+    if download {
+        steps.push(Step::Download);
+    }
+
+    steps.push(match (paths.module.exists(), force) {
+        (false, _) => Step::CreateModule(paths.module.clone()),
+        (true, true) => Step::ReplaceModule(paths.module.clone(), backup_path(&paths.module)),
+        (true, false) => Step::SkipModule(paths.module.clone()),
+    });
+
+    // a missing input is written by the download step. An empty input would hide a sealed copy.
+    let sealed_input = vault::sealed_path(&paths.input);
+    if paths.input.exists() {
+        steps.push(Step::KeepFile(paths.input.clone()));
+    } else if sealed_input.exists() {
+        steps.push(Step::KeepFile(sealed_input));
+    } else if !download {
+        steps.push(Step::CreateFile(paths.input.clone()));
+    }
+
+    steps.push(if is_non_empty_file(&paths.example) {
+        Step::KeepFile(paths.example.clone())
+    } else if download {
+        Step::ExtractExample(paths.example.clone())
+    } else if paths.example.exists() {
+        Step::KeepFile(paths.example.clone())
+    } else {
+        Step::CreateFile(paths.example.clone())
+    });
+
+    steps
+}
+
+/// Values for the placeholders in a module template.
+pub struct TemplateContext {
+    pub day: Day,
+    pub year: u16,
+    pub title: String,
+}
+
+impl TemplateContext {
+    fn get(&self, name: &str) -> Option<String> {
+        match name {
+            "day" => Some(self.day.into_inner().to_string()),
+            "day_padded" => Some(self.day.to_string()),
+            "year" => Some(self.year.to_string()),
+            "title" => Some(self.title.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// Replaces `{{name}}` placeholders in `template`. Whitespace inside the braces is ignored.
+/// `{{{{` is written as a literal `{{`, e.g. for format strings. `}}` outside of placeholders needs no escaping.
+pub fn render(template: &str, context: &TemplateContext) -> Result<String, TemplateError> {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        if rest[start..].starts_with("{{{{") {
+            output.push_str(&rest[..start + 2]);
+            rest = &rest[start + 4..];
+            continue;
+        }
+
+        let line = template[..template.len() - rest.len() + start]
+            .matches('\n')
+            .count()
+            + 1;
+        let end = rest[start..]
+            .find("}}")
+            .ok_or(TemplateError::Unclosed(line))?
+            + start;
+        let name = rest[start + 2..end].trim();
+        let value = context
+            .get(name)
+            .ok_or_else(|| TemplateError::UnknownPlaceholder(name.into(), line))?;
+
+        output.push_str(&rest[..start]);
+        output.push_str(&value);
+        rest = &rest[end + 2..];
+    }
+
+    output.push_str(rest);
+    Ok(output)
+}
+
+/// Template kinds available in the templates directory, sorted by name.
+pub fn available_templates() -> Vec<String> {
+    let mut kinds: Vec<String> = fs::read_dir(&Config::get().paths.templates)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter_map(|entry| {
+                    let name = entry.file_name().to_string_lossy().into_owned();
+                    name.strip_suffix(".rs.tmpl").map(String::from)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    kinds.sort();
+    kinds
+}
+
+/// Loads `templates/<kind>.rs.tmpl`.
+pub fn load_template(kind: &str) -> Result<String, TemplateError> {
+    let path = Config::get()
+        .paths
+        .templates
+        .join(format!("{kind}.rs.tmpl"));
+
+    match fs::read_to_string(path) {
+        Ok(template) => Ok(template),
+        Err(_) if kind == DEFAULT_TEMPLATE => Ok(BUILTIN_TEMPLATE.into()),
+        Err(_) => Err(TemplateError::NotFound(kind.into(), available_templates())),
+    }
+}
+
+/// Extracts the title from the `--- Day N: Title ---` header of a puzzle description.
+pub fn puzzle_title(markdown: &str) -> Option<String> {
+    markdown.lines().find_map(|line| {
+        let line = line.trim_start_matches(['\\', '#', ' ']).trim_end();
+        let title = line.strip_prefix("--- ")?.strip_suffix(" ---")?;
+        title.starts_with("Day ").then(|| title.to_string())
+    })
+}
+
+/// Extracts the example input from a puzzle description.
+///
+/// This is the first code block after the first "For example" paragraph, or the first code block if there is no such paragraph.
+/// Trailing blank lines are dropped to match the existing example files.
+pub fn extract_example(markdown: &str) -> Option<String> {
+    let lines: Vec<&str> = markdown.lines().collect();
+    let start = lines
+        .iter()
+        .position(|line| line.to_lowercase().contains("for example"))
+        .unwrap_or(0);
+
+    let mut blocks = lines[start..].split(|line| line.trim_end() == "```");
+    blocks.next()?;
+    let block = blocks.next()?;
+    // the block has to be closed by another fence.
+    blocks.next()?;
+
+    let example = block.join("\n").trim_end_matches('\n').to_string();
+    (!example.trim().is_empty()).then_some(example)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> TemplateContext {
+        TemplateContext {
+            day: crate::day!(7),
+            year: 2022,
+            title: "Day 7: No Space Left On Device".into(),
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        assert_eq!(
+            render(
+                "//! {{title}}\nread_file(\"inputs\", {{ day }}); // {{day_padded}}/{{year}}\nconst DAYS: u8 = 25;",
+                &context()
+            ),
+            Ok("//! Day 7: No Space Left On Device\nread_file(\"inputs\", 7); // 07/2022\nconst DAYS: u8 = 25;".into())
+        );
+    }
+
+    #[test]
+    fn test_render_escape() {
+        assert_eq!(
+            render("println!(\"{{{{}}\", {{day}}); // {{{{day}}", &context()),
+            Ok("println!(\"{{}}\", 7); // {{day}}".into())
+        );
+    }
+
+    #[test]
+    fn test_render_errors() {
+        assert_eq!(
+            render("ok\n{{ days }}", &context()),
+            Err(TemplateError::UnknownPlaceholder("days".into(), 2))
+        );
+        assert_eq!(render("{{day", &context()), Err(TemplateError::Unclosed(1)));
+    }
+
+    #[test]
+    fn test_builtin_template() {
+        let module = render(BUILTIN_TEMPLATE, &context()).unwrap();
+        assert!(module.contains("advent_of_code::read_file(\"inputs\", advent_of_code::day!(7))"));
+    }
+
+    #[test]
+    fn test_shipped_templates() {
+        let kinds = available_templates();
+        for kind in ["default", "grid", "parser", "simulation"] {
+            assert!(kinds.contains(&kind.to_string()), "missing template {kind}");
+            let module = render(&load_template(kind).unwrap(), &context()).unwrap();
+            assert!(module.starts_with("//! Day 7: No Space Left On Device\n"));
+        }
+        assert!(matches!(
+            load_template("nope"),
+            Err(TemplateError::NotFound(..))
+        ));
+    }
+
+    #[test]
+    fn test_puzzle_title() {
+        assert_eq!(
+            puzzle_title("\\--- Day 1: Calorie Counting ---\n----------\n"),
+            Some("Day 1: Calorie Counting".into())
+        );
+        assert_eq!(
+            puzzle_title("## --- Day 12: Hill Climbing Algorithm ---"),
+            Some("Day 12: Hill Climbing Algorithm".into())
+        );
+        assert_eq!(puzzle_title("no title"), None);
+    }
+
+    #[test]
+    fn test_extract_example() {
+        let markdown = "Intro with `inline` code:\n\n```\nnot this\n```\n\nFor example:\n\n```\n    [D]    \n[N] [C]    \n\nmove 1 from 2 to 1\n\n```\n\nMore text.\n\n```\nlater\n```\n";
+        assert_eq!(
+            extract_example(markdown),
+            Some("    [D]    \n[N] [C]    \n\nmove 1 from 2 to 1".into())
+        );
+        assert_eq!(
+            extract_example("Some text.\n```\n1\n2\n```\n"),
+            Some("1\n2".into())
+        );
+        assert_eq!(extract_example("For example:\n```\nunclosed\n"), None);
+        assert_eq!(extract_example("no code"), None);
+    }
+
+    #[test]
+    fn test_parse_days() {
+        let last_days = vec![crate::day!(23), crate::day!(24), crate::day!(25)];
+        assert_eq!(parse_days("7"), Ok(vec![crate::day!(7)]));
+        assert_eq!(parse_days("23..25"), Ok(last_days.clone()));
+        assert_eq!(parse_days("23..=25"), Ok(last_days));
+        assert_eq!(parse_days("26"), Err(DaysError::OutOfRange(26)));
+        assert_eq!(parse_days("12..3"), Err(DaysError::Empty(12, 3)));
+        assert_eq!(parse_days("x..3"), Err(DaysError::Invalid("x..3".into())));
+    }
+
+    #[test]
+    fn test_backup_path() {
+        assert_eq!(
+            backup_path(Path::new("src/bin/missing.rs")),
+            Path::new("src/bin/missing.rs.bak")
+        );
+    }
+
+    #[test]
+    fn test_plan_sealed_input() {
+        let dir = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let paths = DayPaths {
+            module: dir.join("01.rs"),
+            input: dir.join("01.txt"),
+            example: dir.join("01-example.txt"),
+            puzzle: dir.join("01.md"),
+        };
+        let sealed = vault::sealed_path(&paths.input);
+        fs::write(&sealed, "sealed").unwrap();
+
+        let steps = plan(&paths, false, false);
+        fs::remove_dir_all(&dir).ok();
+        assert_eq!(steps[1], Step::KeepFile(sealed));
+        assert!(!steps.contains(&Step::CreateFile(paths.input.clone())));
+    }
+
+    #[test]
+    fn test_plan() {
+        let missing = DayPaths {
+            module: "src/bin/missing.rs".into(),
+            input: "src/inputs/missing.txt".into(),
+            example: "src/examples/missing.txt".into(),
+            puzzle: "src/puzzles/missing.md".into(),
+        };
+        assert_eq!(
+            plan(&missing, false, false),
+            vec![
+                Step::CreateModule(missing.module.clone()),
+                Step::CreateFile(missing.input.clone()),
+                Step::CreateFile(missing.example.clone()),
+            ]
+        );
+
+        assert_eq!(
+            plan(&missing, true, false),
+            vec![
+                Step::Download,
+                Step::CreateModule(missing.module.clone()),
+                Step::ExtractExample(missing.example.clone()),
+            ]
+        );
+
+        // existing non-empty files are kept, even when forcing or downloading.
+        let existing = DayPaths {
+            module: "src/bin/01.rs".into(),
+            input: "Cargo.toml".into(),
+            example: "src/examples/01.txt".into(),
+            puzzle: "src/puzzles/01.md".into(),
+        };
+        assert_eq!(
+            plan(&existing, true, true),
+            vec![
+                Step::Download,
+                Step::ReplaceModule(existing.module.clone(), "src/bin/01.rs.bak".into()),
+                Step::KeepFile(existing.input.clone()),
+                Step::KeepFile(existing.example.clone()),
+            ]
+        );
+        assert_eq!(
+            plan(&existing, false, false)[0],
+            Step::SkipModule(existing.module.clone())
+        );
+    }
+}
@@ -0,0 +1,168 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{thread, time::Duration};
+
+use crate::calendar;
+
+/// Source of the current unix time in seconds.
+pub trait Clock {
+    fn now(&self) -> u64;
+}
+
+pub trait Sleeper {
+    fn sleep(&self, duration: Duration);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        calendar::unix_now()
+    }
+}
+
+pub struct ThreadSleeper;
+
+impl Sleeper for ThreadSleeper {
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// Formats a countdown as e.g. `1d 02:03:04` or `02:03:04`.
+pub fn format_countdown(secs: u64) -> String {
+    let (days, hours, mins, secs) = (secs / 86400, secs / 3600 % 24, secs / 60 % 60, secs % 60);
+    if days > 0 {
+        format!("{days}d {hours:02}:{mins:02}:{secs:02}")
+    } else {
+        format!("{hours:02}:{mins:02}:{secs:02}")
+    }
+}
+
+/// Sleeps until `grace` seconds after `unlock`, calling `on_tick` with the seconds left until `unlock` about once a second.
+pub fn wait_until(
+    unlock: u64,
+    grace: u64,
+    clock: &impl Clock,
+    sleeper: &impl Sleeper,
+    mut on_tick: impl FnMut(u64),
+) {
+    let target = unlock + grace;
+    loop {
+        let now = clock.now();
+        if now >= target {
+            break;
+        }
+        on_tick(unlock.saturating_sub(now));
+        sleeper.sleep(Duration::from_secs(1));
+    }
+}
+
+/// Calls `op` up to `attempts` times, sleeping between failed attempts.
+/// The delay starts at `initial_delay` and doubles after every attempt.
+pub fn retry_with_backoff<T, E>(
+    attempts: u32,
+    initial_delay: Duration,
+    sleeper: &impl Sleeper,
+    mut op: impl FnMut() -> Result<T, E>,
+    mut on_retry: impl FnMut(&E, Duration),
+) -> Result<T, E> {
+    let mut delay = initial_delay;
+    let mut attempt = 1;
+    loop {
+        match op() {
+            Err(e) if attempt < attempts => {
+                on_retry(&e, delay);
+                sleeper.sleep(delay);
+                delay *= 2;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+
+    #[derive(Default)]
+    struct FakeTime {
+        now: Cell<u64>,
+        sleeps: RefCell<Vec<Duration>>,
+    }
+
+    impl Clock for FakeTime {
+        fn now(&self) -> u64 {
+            self.now.get()
+        }
+    }
+
+    impl Sleeper for FakeTime {
+        fn sleep(&self, duration: Duration) {
+            self.now.set(self.now.get() + duration.as_secs());
+            self.sleeps.borrow_mut().push(duration);
+        }
+    }
+
+    #[test]
+    fn test_wait_until() {
+        let time = FakeTime::default();
+        time.now.set(997);
+
+        let mut ticks = vec![];
+        wait_until(1000, 2, &time, &time, |left| ticks.push(left));
+
+        assert_eq!(ticks, vec![3, 2, 1, 0, 0]);
+        assert_eq!(time.now(), 1002);
+    }
+
+    #[test]
+    fn test_wait_until_unlocked() {
+        let time = FakeTime::default();
+        time.now.set(2000);
+        wait_until(1000, 5, &time, &time, |_| panic!("should not wait"));
+        assert!(time.sleeps.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_retry_with_backoff() {
+        let time = FakeTime::default();
+        let mut calls = 0;
+        let result = retry_with_backoff(
+            5,
+            Duration::from_secs(2),
+            &time,
+            || {
+                calls += 1;
+                if calls < 3 {
+                    Err(calls)
+                } else {
+                    Ok(calls)
+                }
+            },
+            |_, _| {},
+        );
+
+        assert_eq!(result, Ok(3));
+        assert_eq!(
+            *time.sleeps.borrow(),
+            vec![Duration::from_secs(2), Duration::from_secs(4)]
+        );
+
+        let result: Result<(), _> =
+            retry_with_backoff(3, Duration::from_secs(1), &time, || Err("nope"), |_, _| {});
+        assert_eq!(result, Err("nope"));
+        assert_eq!(time.sleeps.borrow().len(), 4);
+    }
+
+    #[test]
+    fn test_format_countdown() {
+        assert_eq!(format_countdown(5), "00:00:05");
+        assert_eq!(format_countdown(3 * 3600 + 62), "03:01:02");
+        assert_eq!(format_countdown(90_000), "1d 01:00:00");
+    }
+}
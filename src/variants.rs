@@ -0,0 +1,175 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use std::{
+    fmt::{Display, Write},
+    time::{Duration, Instant},
+};
+
+use crate::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Each variant runs until it took this long in total, or for `MAX_RUNS` runs.
+const BUDGET: Duration = Duration::from_millis(500);
+const MAX_RUNS: u32 = 100;
+
+/// A named implementation of a part.
+pub type Variant<I, T> = (&'static str, fn(I) -> Option<T>);
+
+/// Whether `--all-variants` was passed to the solution.
+pub fn all_variants() -> bool {
+    std::env::args()
+        .skip(1)
+        .take_while(|arg| arg != "--")
+        .any(|arg| arg == "--all-variants")
+}
+
+/// Answer and timings of one implementation of a part.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report<T> {
+    pub name: &'static str,
+    pub answer: Option<T>,
+    pub runs: u32,
+    pub mean: Duration,
+    pub min: Duration,
+}
+
+/// Runs every variant until its time budget is used up. `make_input` is called before every run,
+/// so inputs that are consumed, like readers, are opened again.
+pub fn benchmark<I, T>(
+    variants: &[Variant<I, T>],
+    mut make_input: impl FnMut() -> I,
+) -> Vec<Report<T>> {
+    variants
+        .iter()
+        .map(|(name, solver)| {
+            let mut answer = None;
+            let mut total = Duration::ZERO;
+            let mut min = Duration::MAX;
+            let mut runs = 0;
+
+            while runs < MAX_RUNS && total < BUDGET {
+                let input = make_input();
+                let timer = Instant::now();
+                let result = solver(input);
+                let elapsed = timer.elapsed();
+
+                answer.get_or_insert(result);
+                total += elapsed;
+                min = min.min(elapsed);
+                runs += 1;
+            }
+
+            Report {
+                name,
+                answer: answer.flatten(),
+                runs,
+                mean: total / runs,
+                min,
+            }
+        })
+        .collect()
+}
+
+/// Names of the variants whose answer differs from the first one.
+pub fn disagreeing<T: PartialEq>(reports: &[Report<T>]) -> Vec<&'static str> {
+    match reports.split_first() {
+        Some((first, rest)) => rest
+            .iter()
+            .filter(|report| report.answer != first.answer)
+            .map(|report| report.name)
+            .collect(),
+        None => vec![],
+    }
+}
+
+/// One line per variant with its answer and timings, relative to the fastest one.
+pub fn format_reports<T: Display>(reports: &[Report<T>]) -> String {
+    let fastest = reports.iter().map(|report| report.mean).min();
+    // very fast variants can round down to zero.
+    let fastest = fastest.map_or(1e-9, |mean| mean.as_secs_f64().max(1e-9));
+    let name_width = reports.iter().map(|report| report.name.len()).max();
+    let answers: Vec<String> = reports
+        .iter()
+        .map(|report| match &report.answer {
+            Some(answer) => answer.to_string(),
+            None => "not solved.".into(),
+        })
+        .collect();
+    let answer_width = answers.iter().map(|answer| answer.len()).max();
+
+    let mut output = String::new();
+    for (report, answer) in reports.iter().zip(&answers) {
+        // multi-line answers, like block letters, are too wide for the table.
+        let answer = match answer.contains('\n') {
+            true => "(multi-line)",
+            false => answer,
+        };
+        let relative = report.mean.as_secs_f64().max(1e-9) / fastest;
+        writeln!(
+            output,
+            "{ANSI_BOLD}{:<name$}{ANSI_RESET}  {:<answer$}  {ANSI_ITALIC}mean {:.2?}, min {:.2?}, {} runs, {:.2}x{ANSI_RESET}",
+            report.name,
+            answer,
+            report.mean,
+            report.min,
+            report.runs,
+            relative,
+            name = name_width.unwrap(),
+            answer = answer_width.unwrap(),
+        )
+        .unwrap();
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn double(input: u32) -> Option<u32> {
+        Some(input * 2)
+    }
+
+    fn shift(input: u32) -> Option<u32> {
+        Some(input << 1)
+    }
+
+    fn square(input: u32) -> Option<u32> {
+        Some(input * input)
+    }
+
+    #[test]
+    fn test_benchmark() {
+        let mut inputs = 0;
+        let reports = benchmark(&[("double", double), ("shift", shift)], || {
+            inputs += 1;
+            21
+        });
+
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].answer, Some(42));
+        assert_eq!(reports[1].runs, MAX_RUNS);
+        assert_eq!(inputs, 2 * MAX_RUNS);
+        assert!(reports[0].min <= reports[0].mean);
+        assert!(disagreeing(&reports).is_empty());
+    }
+
+    #[test]
+    fn test_disagreeing() {
+        let reports = benchmark(
+            &[("double", double), ("square", square), ("shift", shift)],
+            || 3,
+        );
+        assert_eq!(disagreeing(&reports), ["square"]);
+    }
+
+    #[test]
+    fn test_format_reports() {
+        let reports = benchmark(&[("double", double), ("shift", shift)], || 5);
+        let output = format_reports(&reports);
+        assert_eq!(output.lines().count(), 2);
+        assert!(output.contains("shift"));
+        assert!(output.contains("10"));
+    }
+}
@@ -0,0 +1,203 @@
+/*
+ * This file contains template code.
+ * There is no need to edit this file unless you want to change template functionality.
+ */
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use std::{
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::config::Config;
+
+/// Extension appended to sealed files, e.g. `01.txt.enc`.
+pub const SEALED_EXTENSION: &str = "enc";
+
+/// Identifies the file format, so that a future format change can be detected.
+const MAGIC: &[u8] = b"AOCVAULT1";
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug)]
+pub enum VaultError {
+    KeyNotFound(PathBuf),
+    KeyExists(PathBuf),
+    InvalidKey,
+    InvalidFormat,
+    DecryptionFailed,
+    IoError(PathBuf),
+}
+
+impl Display for VaultError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VaultError::KeyNotFound(path) => write!(
+                f,
+                "key file \"{}\" not found. Run `cargo vault init` or copy the key from a teammate.",
+                path.display()
+            ),
+            VaultError::KeyExists(path) => {
+                write!(f, "key file \"{}\" already exists.", path.display())
+            }
+            VaultError::InvalidKey => write!(f, "key file should contain 64 hex characters."),
+            VaultError::InvalidFormat => write!(f, "file is not a sealed vault file."),
+            VaultError::DecryptionFailed => {
+                write!(f, "could not decrypt file, was it sealed with a different key?")
+            }
+            VaultError::IoError(path) => write!(f, "could not access \"{}\".", path.display()),
+        }
+    }
+}
+
+pub struct Vault {
+    cipher: ChaCha20Poly1305,
+}
+
+impl Vault {
+    pub fn from_hex(hex: &str) -> Result<Self, VaultError> {
+        let hex = hex.trim();
+        if hex.len() != 64 || !hex.is_ascii() {
+            return Err(VaultError::InvalidKey);
+        }
+
+        let key = (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| VaultError::InvalidKey)?;
+
+        Ok(Self {
+            cipher: ChaCha20Poly1305::new(Key::from_slice(&key)),
+        })
+    }
+
+    /// Loads the key file configured in `vault.key_file`.
+    pub fn load() -> Result<Self, VaultError> {
+        let path = &Config::get().vault_key_file;
+        if !path.is_file() {
+            return Err(VaultError::KeyNotFound(path.clone()));
+        }
+        let hex = fs::read_to_string(path).map_err(|_| VaultError::IoError(path.clone()))?;
+        Self::from_hex(&hex)
+    }
+
+    /// Generates a new random key, formatted as hex.
+    pub fn generate_key() -> String {
+        ChaCha20Poly1305::generate_key(&mut OsRng)
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+
+    /// Writes a new key file. Refuses to overwrite an existing key, since files sealed with it would be lost.
+    pub fn init() -> Result<PathBuf, VaultError> {
+        let path = &Config::get().vault_key_file;
+        if path.exists() {
+            return Err(VaultError::KeyExists(path.clone()));
+        }
+        fs::write(path, Self::generate_key() + "\n")
+            .map_err(|_| VaultError::IoError(path.clone()))?;
+        Ok(path.clone())
+    }
+
+    /// Encrypts `plaintext` as `MAGIC | nonce | ciphertext`, using a fresh random nonce.
+    pub fn seal(&self, plaintext: &[u8]) -> Vec<u8> {
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plaintext)
+            .expect("encryption of in-memory data does not fail");
+
+        [MAGIC, nonce.as_slice(), &ciphertext].concat()
+    }
+
+    pub fn unseal(&self, sealed: &[u8]) -> Result<Vec<u8>, VaultError> {
+        let rest = sealed
+            .strip_prefix(MAGIC)
+            .filter(|rest| rest.len() >= NONCE_LEN)
+            .ok_or(VaultError::InvalidFormat)?;
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+        self.cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| VaultError::DecryptionFailed)
+    }
+
+    pub fn unseal_file(&self, path: &Path) -> Result<String, VaultError> {
+        let sealed = fs::read(path).map_err(|_| VaultError::IoError(path.to_path_buf()))?;
+        let plaintext = self.unseal(&sealed)?;
+        String::from_utf8(plaintext).map_err(|_| VaultError::InvalidFormat)
+    }
+}
+
+/// Path of the sealed counterpart of `path`, e.g. `src/inputs/01.txt.enc`.
+pub fn sealed_path(path: &Path) -> PathBuf {
+    let mut sealed = path.as_os_str().to_owned();
+    sealed.push(".");
+    sealed.push(SEALED_EXTENSION);
+    PathBuf::from(sealed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    #[test]
+    fn test_seal_roundtrip() {
+        let vault = Vault::from_hex(KEY).unwrap();
+        let sealed = vault.seal(b"1000\n2000\n\n3000\n");
+
+        assert!(sealed.starts_with(MAGIC));
+        assert_eq!(vault.unseal(&sealed).unwrap(), b"1000\n2000\n\n3000\n");
+        // a fresh nonce is used for every seal.
+        assert_ne!(vault.seal(b"1000"), vault.seal(b"1000"));
+    }
+
+    #[test]
+    fn test_unseal_errors() {
+        let vault = Vault::from_hex(KEY).unwrap();
+        let other = Vault::from_hex(&Vault::generate_key()).unwrap();
+        let mut sealed = vault.seal(b"input");
+
+        assert!(matches!(
+            other.unseal(&sealed),
+            Err(VaultError::DecryptionFailed)
+        ));
+        assert!(matches!(
+            vault.unseal(b"input"),
+            Err(VaultError::InvalidFormat)
+        ));
+
+        let last = sealed.len() - 1;
+        sealed[last] ^= 1;
+        assert!(matches!(
+            vault.unseal(&sealed),
+            Err(VaultError::DecryptionFailed)
+        ));
+    }
+
+    #[test]
+    fn test_invalid_key() {
+        assert!(matches!(
+            Vault::from_hex("abc"),
+            Err(VaultError::InvalidKey)
+        ));
+        assert!(matches!(
+            Vault::from_hex(&"zz".repeat(32)),
+            Err(VaultError::InvalidKey)
+        ));
+    }
+
+    #[test]
+    fn test_sealed_path() {
+        assert_eq!(
+            sealed_path(Path::new("src/inputs/01.txt")),
+            Path::new("src/inputs/01.txt.enc")
+        );
+    }
+}